@@ -11,27 +11,37 @@ fn parse_test(
     chars: &str,
     tester: impl FnOnce(&mut terl::Parser<py_lex::Token>) -> terl::Result<(), terl::ParseError>,
 ) {
-    use terl::{Buffer, ResultMapperExt, Source};
+    parse_test_with(py_lex::LexMode::Ascii, chars, tester)
+}
+
+#[cfg(test)]
+fn parse_test_with(
+    mode: py_lex::LexMode,
+    chars: &str,
+    tester: impl FnOnce(&mut terl::Parser<py_lex::Token>) -> terl::Result<(), terl::ParseError>,
+) {
+    use terl::{Buffer, Source};
 
     let source = Buffer::new("test.py1".to_string(), chars.chars().collect());
     let parser = terl::Parser::<char>::new(source);
     let (char_buffer, mut parser) = parser
-        .process(|p| {
-            let mut tokens = vec![];
-
-            while let Some(token) = p.parse::<py_lex::Token>().apply(terl::mapper::Try)? {
-                tokens.push(token);
-            }
-            Ok(tokens)
-        })
+        .process(|p| py_lex::Tokens::parse_with(p, mode.into()))
         .unwrap_or_else(|_| unreachable!());
 
-    if let Err(error) = tester(&mut parser) {
+    // errors which the parser recovered from are failures too
+    let errors = match tester(&mut parser) {
+        Ok(()) => parser.take_reported(),
+        Err(error) => vec![error],
+    };
+    if !errors.is_empty() {
         let calling_tree = parser.calling_tree();
         eprintln!("{calling_tree}");
-        eprintln!("error: {:?}", error);
-        let error = py_lex::Token::handle_error(&(&char_buffer, parser.buffer()), error.error());
-        eprintln!("{error}");
+        for error in errors {
+            eprintln!("error: {:?}", error);
+            let error =
+                py_lex::Token::handle_error(&(&char_buffer, parser.buffer()), error.error());
+            eprintln!("{error}");
+        }
         panic!("panic as expected")
     }
 }
@@ -1,24 +1,52 @@
 use py_lex::{
-    ops::{OperatorAssociativity, OperatorTypes, Operators},
+    ops::{Associativity, OperatorAssociativity, Operators},
     syntax::*,
 };
 
 use super::*;
 use crate::complex_pu;
+use crate::ir::types::PrimitiveType;
 
 #[derive(Debug, Clone)]
 pub struct CharLiteral {
     pub parsed: char,
 }
 
-fn escape(src: &Token, c: char) -> Result<char> {
-    Result::Ok(match c {
-        '_' => '_',
-        't' => '\t',
-        'n' => '\n',
-        's' => ' ',
+/// escape the characters after `_`
+///
+/// * `__`: `_`
+/// * `_t`, `_n`, `_r`, `_0`: tab, newline, carriage return and nul
+/// * `_s`: space
+/// * `_uXXXX` and `_UXXXXXXXX`: a unicode code point in hex, like `_u4e2d` for `中`
+///
+/// characters which are separators for the tokenizer (like `,` and `.`) can only be written
+/// in code point escapes, like `_u002c` for `,`
+fn escape(src: &PU<Token>, chars: &mut std::str::Chars) -> Result<char> {
+    let Some(c) = chars.next() else {
+        return src.throw("Invalid escape! maybe you losted a character");
+    };
+    let len = match c {
+        '_' => return Result::Ok('_'),
+        't' => return Result::Ok('\t'),
+        'n' => return Result::Ok('\n'),
+        'r' => return Result::Ok('\r'),
+        '0' => return Result::Ok('\0'),
+        's' => return Result::Ok(' '),
+        'u' => 4,
+        'U' => 8,
         _ => return src.throw(format!("Invalid or unsupported escape character: {}", c)),
-    })
+    };
+
+    let code = chars.take(len).collect::<String>();
+    if code.len() != len || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+        return src.throw(format!(
+            "expect {len} hex digits after `_{c}`, but got `{code}`"
+        ));
+    }
+    match char::from_u32(u32::from_str_radix(&code, 16).unwrap()) {
+        Some(c) => Result::Ok(c),
+        None => src.throw(format!("`{code}` is not a valid unicode code point")),
+    }
 }
 
 impl ParseUnit<Token> for CharLiteral {
@@ -26,15 +54,17 @@ impl ParseUnit<Token> for CharLiteral {
 
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
         p.r#match(Symbol::Char)?;
-        let unparsed = p.parse::<Token>()?;
-        if !(unparsed.len() == 1 || unparsed.len() == 2 && unparsed.starts_with('_')) {
-            return unparsed.throw(format!("Invalid CharLiteral {}", unparsed));
-        }
-        let parsed = if unparsed.len() == 1 {
-            unparsed.as_bytes()[0] as char
-        } else {
-            escape(&unparsed, unparsed.as_bytes()[1] as _)?
+        let unparsed = p.parse::<PU<Token>>()?;
+
+        let mut chars = unparsed.chars();
+        let parsed = match chars.next() {
+            Some('_') => escape(&unparsed, &mut chars)?,
+            Some(c) => c,
+            None => unreachable!("tokens are never empty"),
         };
+        if chars.next().is_some() {
+            return unparsed.throw(format!("Invalid CharLiteral {}", *unparsed));
+        }
 
         Ok(CharLiteral { parsed })
     }
@@ -50,64 +80,194 @@ impl ParseUnit<Token> for StringLiteral {
 
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
         p.r#match(Symbol::String)?;
-        let unparsed = p.parse::<Token>()?;
+        let unparsed = p.parse::<PU<Token>>()?;
 
-        let mut next_escape = false;
         let mut parsed = String::new();
-        for c in unparsed.chars() {
-            if next_escape {
-                next_escape = false;
-                parsed.push(escape(&unparsed, c)?);
-            } else if c == '_' {
-                next_escape = true
+        let mut chars = unparsed.chars();
+        while let Some(c) = chars.next() {
+            if c == '_' {
+                parsed.push(escape(&unparsed, &mut chars)?);
             } else {
                 parsed.push(c)
             }
         }
-        if next_escape {
-            return unparsed.throw("Invalid escape! maybe you losted a character");
-        }
 
         Ok(StringLiteral { parsed })
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoolLiteral {
+    pub parsed: bool,
+}
+
+impl ParseUnit<Token> for BoolLiteral {
+    type Target = BoolLiteral;
+
+    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
+        if p.r#match(Literals::True).apply(mapper::Try)?.is_some() {
+            return Ok(BoolLiteral { parsed: true });
+        }
+        p.r#match(Literals::False)?;
+        Ok(BoolLiteral { parsed: false })
+    }
+}
+
+/// `kong1zhi3`, the null pointer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NullLiteral;
+
+impl ParseUnit<Token> for NullLiteral {
+    type Target = NullLiteral;
+
+    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
+        p.r#match(Literals::Null)?;
+        Ok(NullLiteral)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum NumberLiteral {
+pub enum Number {
     Float(f64),
-    Digit(usize),
+    Digit(u128),
 }
 
-impl ParseUnit<Token> for NumberLiteral {
-    type Target = NumberLiteral;
+/// number literals:
+///
+/// * integers: `114514`, `0x1bf52`, `0b1010`, `0o777`, `_` can be used to separate digits
+/// * floats: `1919f810`(1919.810), `114514f`, with exponents: `1f5e3`(1.5e3), `15en1`(15e-1)
+/// * an optional suffix which pins the type of the literal: `300u16`, `0xffi32`, `1f5_f64`
+///
+/// because `f` is the decimal point, float suffixes must follow a `_`, fraction, or exponent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberLiteral {
+    pub number: Number,
+    pub suffix: Option<PrimitiveType>,
+}
 
-    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
-        let number = p.parse::<Token>()?; // digit
-        let mut int_dec = number.split('f');
+fn is_digit_or_separator(c: char) -> bool {
+    c.is_ascii_digit() || c == '_'
+}
 
-        let Some(int) = int_dec.next() else {
-            unreachable!()
+fn parse_integer(src: &PU<Token>, digits: &str, radix: u32) -> Result<u128> {
+    let mut int: u128 = 0;
+    let mut empty = true;
+    for c in digits.chars().filter(|c| *c != '_') {
+        let Some(digit) = c.to_digit(radix) else {
+            return src.throw(format!(
+                "invalid digit `{c}` in a number with radix {radix}"
+            ));
         };
-        let int = match int.parse::<usize>() {
-            Ok(int) => int,
-            Err(e) => return p.unmatch(e),
+        empty = false;
+        int = match int
+            .checked_mul(radix as u128)
+            .and_then(|int| int.checked_add(digit as u128))
+        {
+            Some(int) => int,
+            None => return src.throw("integer literal is too large even for u128"),
         };
+    }
+    if empty {
+        return src.throw("expect digits in the number");
+    }
+    Ok(int)
+}
 
-        let dec = match int_dec.next() {
-            Some("") => 0.0,
-            Some(dec) => match dec.parse::<usize>() {
-                Ok(0) => 0.0,
-                Ok(dec) => dec as f64 / 10f64.powi(dec.ilog10() as _),
-                Err(e) => return p.unmatch(e),
-            },
-            None => return Ok(Self::Digit(int)),
+fn parse_suffix(src: &PU<Token>, suffix: &str) -> Result<Option<PrimitiveType>> {
+    let suffix = suffix.strip_prefix('_').unwrap_or(suffix);
+    if suffix.is_empty() {
+        return Ok(None);
+    }
+    match suffix.parse::<PrimitiveType>() {
+        Ok(PrimitiveType::Bool) | Err(_) => src.throw(format!("unknown number suffix `{suffix}`")),
+        Ok(ty) => Ok(Some(ty)),
+    }
+}
+
+/// split `src` into the leading digits(with separators) and the rest
+fn take_digits(src: &str) -> (&str, &str) {
+    src.split_at(src.find(|c| !is_digit_or_separator(c)).unwrap_or(src.len()))
+}
+
+impl NumberLiteral {
+    /// `0x`, `0b` or `0o` integers, only integer suffixes are allowed
+    fn parse_radix(src: &PU<Token>, radix: u32) -> Result<Self> {
+        let body = &src.string[2..];
+        let (digits, suffix) = body.split_at(body.find(['i', 'u']).unwrap_or(body.len()));
+        let number = Number::Digit(parse_integer(src, digits, radix)?);
+        let suffix = parse_suffix(src, suffix)?;
+        Ok(NumberLiteral { number, suffix })
+    }
+
+    fn parse_decimal(src: &PU<Token>) -> Result<Self> {
+        let (int, mut rest) = take_digits(&src.string);
+
+        let mut is_float = false;
+        let mut frac = "";
+        // `f` is the decimal point, unless it is a float suffix just after a `_`
+        if rest.starts_with('f') && !(int.ends_with('_') && matches!(rest, "f32" | "f64")) {
+            is_float = true;
+            (frac, rest) = take_digits(&rest[1..]);
+        }
+
+        let mut exp = None;
+        if let Some(after_e) = rest.strip_prefix('e') {
+            is_float = true;
+            let (negative, after_e) = match after_e.strip_prefix('n') {
+                Some(after_e) => (true, after_e),
+                None => (false, after_e),
+            };
+            let digits;
+            (digits, rest) = take_digits(after_e);
+            if digits.is_empty() {
+                return src.throw("expect digits after the exponent `e`");
+            }
+            exp = Some((negative, digits));
+        }
+
+        let suffix = parse_suffix(src, rest)?;
+        let float_suffix = suffix.map(|ty| ty.is_float());
+
+        let number = if is_float || float_suffix == Some(true) {
+            if float_suffix == Some(false) {
+                let suffix = suffix.unwrap();
+                return src.throw(format!("float number cant have integer suffix `{suffix}`"));
+            }
+            // let std do exact decimal parsing
+            let strip = |s: &str| s.chars().filter(|c| *c != '_').collect::<String>();
+            let mut float = format!("{}.{}", strip(int), strip(frac));
+            if let Some((negative, exp)) = exp {
+                float += if negative { "e-" } else { "e" };
+                float += &strip(exp);
+            }
+            match float.parse::<f64>() {
+                Ok(float) if float.is_finite() => Number::Float(float),
+                _ => return src.throw("float number is out of range"),
+            }
+        } else {
+            Number::Digit(parse_integer(src, int, 10)?)
         };
-        // check that if anything left
-        if let Some(next) = int_dec.next() {
-            return p.unmatch(format!("unexpect {}", next));
+
+        Ok(NumberLiteral { number, suffix })
+    }
+}
+
+impl ParseUnit<Token> for NumberLiteral {
+    type Target = NumberLiteral;
+
+    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
+        let number = p.parse::<PU<Token>>()?; // digit
+        if !number.starts_with(|c: char| c.is_ascii_digit()) {
+            return p.unmatch(format!("expect a number, but {} was got", number));
         }
 
-        Ok(Self::Float(int as f64 + dec))
+        // idents cant start with a digit, so the token must be a number
+        match number.get(..2) {
+            Some("0x") => Self::parse_radix(&number, 16),
+            Some("0b") => Self::parse_radix(&number, 2),
+            Some("0o") => Self::parse_radix(&number, 8),
+            _ => Self::parse_decimal(&number),
+        }
     }
 }
 
@@ -208,142 +368,130 @@ complex_pu! {
         CharLiteral,
         StringLiteral,
         NumberLiteral,
+        BoolLiteral,
+        NullLiteral,
         FnCall,
         Array,
         Variable
     }
 }
 
+/// an expression tree
+///
+/// brackets are not kept, they only change the shape of the tree
 #[derive(Debug, Clone)]
-pub enum ExprItem {
-    AtomicExpr(PU<AtomicExpr>),
-    Operators(PU<Operators>),
+pub enum Expr {
+    Atomic(PU<AtomicExpr>),
+    Unary {
+        op: PU<Operators>,
+        val: Box<Expr>,
+    },
+    Binary {
+        op: PU<Operators>,
+        l: Box<Expr>,
+        r: Box<Expr>,
+    },
+    /// `ruo4 can1 $conds jie2 $then ze2 $else`
+    Conditional {
+        /// the span of `ruo4`
+        if_span: Span,
+        conds: Conditions,
+        then: Box<Expr>,
+        else_: Box<Expr>,
+    },
 }
 
-impl WithSpan for ExprItem {
+impl WithSpan for Expr {
     fn get_span(&self) -> Span {
         match self {
-            ExprItem::AtomicExpr(ws) => ws.get_span(),
-            ExprItem::Operators(ws) => ws.get_span(),
+            Expr::Atomic(atomic) => atomic.get_span(),
+            Expr::Unary { op, val } => op.get_span().merge(val.get_span()),
+            Expr::Binary { l, r, .. } => l.get_span().merge(r.get_span()),
+            Expr::Conditional { if_span, else_, .. } => if_span.merge(else_.get_span()),
         }
     }
 }
 
-impl From<PU<Operators>> for ExprItem {
-    fn from(v: PU<Operators>) -> Self {
-        Self::Operators(v)
-    }
-}
-
-impl From<PU<AtomicExpr>> for ExprItem {
-    fn from(v: PU<AtomicExpr>) -> Self {
-        Self::AtomicExpr(v)
-    }
-}
-
-#[derive(Debug, Clone)]
-struct ExprItems;
+impl Expr {
+    /// an operand: an atomic expression, a bracket, a conditional expression, or an unary
+    /// operator with its operand
+    fn parse_operand(p: &mut Parser<Token>) -> Result<Expr> {
+        if let Some(left_bracket) = p.r#match(RPU(Operators::BracketL)).apply(mapper::Try)? {
+            let expr = Self::parse_binary(p, usize::MAX)?;
+            if p.r#match(Operators::BracketR).apply(mapper::Try)?.is_none() {
+                let current_span = p.get_span();
+                let expect_next = format!("expect this to be `{}`", Operators::BracketR);
+                let expect_next = p
+                    .parse::<PU<Token>>()
+                    .map(|tk| tk.make_message(expect_next));
+                return current_span.throw("unclosed bracket").map_err(|mut e| {
+                    e.extend(Some(left_bracket.make_message("left bracket here")));
+                    e.extend(expect_next.ok());
+                    e
+                });
+            }
+            return Ok(expr);
+        }
 
-impl ParseUnit<Token> for ExprItems {
-    type Target = Vec<ExprItem>;
+        if let Some(if_) = p.r#match(RPU(ControlFlow::If)).apply(mapper::Try)? {
+            // like `?:` in c, `else` takes as much as it can, so `ruo4 .. a ze2 b jia1 1` is
+            // `ruo4 .. a ze2 (b jia1 1)`
+            let conds = Conditions::parse_if(p).apply(mapper::MustMatch)?;
+            let then = Self::parse_binary(p, usize::MAX).apply(mapper::MustMatch)?;
+            p.r#match(ControlFlow::Else).apply(mapper::MustMatch)?;
+            let else_ = Self::parse_binary(p, usize::MAX).apply(mapper::MustMatch)?;
+            return Ok(Expr::Conditional {
+                if_span: if_.get_span(),
+                conds,
+                then: Box::new(then),
+                else_: Box::new(else_),
+            });
+        }
 
-    fn parse(p: &mut Parser<Token>) -> terl::Result<Self::Target, ParseError> {
         let get_unary_op = |p: &mut Parser<Token>| {
             p.parse::<PU<Operators>>().apply(mapper::Satisfy::new(
                 |op: &PU<Operators>| op.associativity() == OperatorAssociativity::Unary,
                 |e| e.unmatch(""),
             ))
         };
+        if let Some(op) = p.once(get_unary_op).apply(mapper::Try)? {
+            // `fu4 a jia1 b` is `(fu4 a) jia1 b`
+            let val = Box::new(Self::parse_binary(p, op.priority())?);
+            return Ok(Expr::Unary { op, val });
+        }
+
+        Ok(Expr::Atomic(p.parse::<PU<AtomicExpr>>()?))
+    }
+
+    /// parse an expression whose binary operators have higher priority than `limit`
+    ///
+    /// samller number means higher priority, see [`Operators::priority`]
+    fn parse_binary(p: &mut Parser<Token>, limit: usize) -> Result<Expr> {
         let get_binary_op = |p: &mut Parser<Token>| {
             p.parse::<PU<Operators>>().apply(mapper::Satisfy::new(
-                |op: &PU<Operators>| op.associativity() == OperatorAssociativity::Binary,
+                |op: &PU<Operators>| {
+                    op.associativity() == OperatorAssociativity::Binary && op.priority() < limit
+                },
                 |e| e.unmatch(""),
             ))
         };
 
-        let left_bracket = |items: &[ExprItem], nth: usize| {
-            items
-                .iter()
-                .rev()
-                .filter_map(|item| match item {
-                    ExprItem::Operators(pu) if **pu == Operators::BracketL => Some(item.get_span()),
-                    _ => None,
-                })
-                .nth(nth)
-                .map(|span| span.make_message("left bracket here"))
-        };
-
-        enum Expect {
-            Val,
-            OP,
-        }
-        let mut items: Vec<ExprItem> = vec![];
-        let mut bracket_depth = 0;
-        let mut state = Expect::Val;
-        loop {
-            state = match state {
-                Expect::Val => {
-                    if let Some(lb) = p.r#match(RPU(Operators::BracketL)).apply(mapper::Try)? {
-                        items.push(lb.into());
-                        bracket_depth += 1;
-                        Expect::Val
-                    } else if let Some(unary) = p.once(get_unary_op).apply(mapper::Try)? {
-                        items.push(unary.into());
-                        Expect::Val
-                    } else {
-                        items.push(p.parse::<PU<AtomicExpr>>()?.into());
-                        Expect::OP
-                    }
-                }
-                Expect::OP => {
-                    if bracket_depth != 0
-                        && let Some(rb) = p.r#match(RPU(Operators::BracketR)).apply(mapper::Try)?
-                    {
-                        items.push(rb.into());
-
-                        bracket_depth -= 1;
-                        Expect::OP
-                    } else if let Some(unary) = p.once(get_binary_op).apply(mapper::Try)? {
-                        items.push(unary.into());
-                        Expect::Val
-                    } else if bracket_depth != 0 {
-                        let left_bracket = left_bracket(&items, bracket_depth);
-                        let current_span = p.get_span();
-                        let expect_next = format!("expect this to be `{}`", Operators::BracketR);
-                        let expect_next = p
-                            .parse::<PU<Token>>()
-                            .map(|tk| tk.make_message(expect_next));
-                        break current_span.throw("unclosed bracket").map_err(|mut e| {
-                            e.extend(left_bracket);
-                            e.extend(expect_next.ok());
-                            e
-                        });
-                    } else {
-                        break Ok(items);
-                    }
-                }
-            }
+        let mut l = Self::parse_operand(p)?;
+        while let Some(op) = p.once(get_binary_op).apply(mapper::Try)? {
+            // the right side of a right associative operator takes operators with the same
+            // priority, like `a mi4 b mi4 c` is `a mi4 (b mi4 c)`
+            let r_limit = match op.grouping() {
+                Associativity::Left => op.priority(),
+                Associativity::Right => op.priority() + 1,
+            };
+            let r = Self::parse_binary(p, r_limit)?;
+            l = Expr::Binary {
+                op,
+                l: Box::new(l),
+                r: Box::new(r),
+            };
         }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct Expr {
-    items: Vec<ExprItem>,
-    span: Span,
-}
-
-impl WithSpan for Expr {
-    fn get_span(&self) -> Span {
-        self.span
-    }
-}
-
-impl std::ops::Deref for Expr {
-    type Target = Vec<ExprItem>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.items
+        Ok(l)
     }
 }
 
@@ -351,50 +499,7 @@ impl ParseUnit<Token> for Expr {
     type Target = Expr;
 
     fn parse(p: &mut Parser<Token>) -> terl::Result<Self::Target, ParseError> {
-        // is heap allocation fewer than previous algo?
-        let mut exprs = vec![];
-        let mut ops: Vec<PU<Operators>> = vec![];
-
-        fn could_fold(last: Operators, current: Operators) -> bool {
-            last.op_ty() != OperatorTypes::StructOperator && last.priority() <= current.priority()
-        }
-
-        for item in p.parse::<ExprItems>()? {
-            match item {
-                ExprItem::AtomicExpr(..) => {
-                    exprs.push(item);
-                }
-                ExprItem::Operators(op) => match *op {
-                    Operators::BracketL => ops.push(PU::new(item.get_span(), *op)),
-                    Operators::BracketR => {
-                        while let Some(op) = ops.pop() {
-                            if *op == Operators::BracketL {
-                                break;
-                            }
-                            exprs.push(op.into())
-                        }
-                    }
-                    current => {
-                        while ops.last().is_some_and(|last| {
-                            could_fold(**last, current) && exprs.len() >= last.cost()
-                        }) {
-                            let last = ops.pop().unwrap();
-                            exprs.push(last.into());
-                        }
-                        ops.push(PU::new(item.get_span(), *op));
-                    }
-                },
-            }
-        }
-
-        for op in ops.into_iter().rev() {
-            exprs.push(op.into());
-        }
-
-        Ok(Self {
-            items: exprs,
-            span: p.get_span(),
-        })
+        Self::parse_binary(p, usize::MAX)
     }
 }
 
@@ -419,6 +524,48 @@ mod tests {
         })
     }
 
+    #[test]
+    fn unicode_char() {
+        parse_test("wen2 中", |p| {
+            assert_eq!(p.parse::<CharLiteral>()?.parsed, '中');
+            Ok(())
+        });
+        parse_test("wen2 _u4e2d", |p| {
+            assert_eq!(p.parse::<CharLiteral>()?.parsed, '中');
+            Ok(())
+        });
+        parse_test("wen2 _U0001f600", |p| {
+            assert_eq!(p.parse::<CharLiteral>()?.parsed, '😀');
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn unicode_string() {
+        parse_test("chuan4 你好_u002c_s世界_u0021", |p| {
+            assert_eq!(p.parse::<StringLiteral>()?.parsed, "你好, 世界!");
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_code_point() {
+        parse_test("wen2 _ud800", |p| {
+            p.parse::<CharLiteral>()?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_code_point() {
+        parse_test("chuan4 _u4e", |p| {
+            p.parse::<StringLiteral>()?;
+            Ok(())
+        })
+    }
+
     #[test]
     fn number1() {
         parse_test("114514", |p| {
@@ -443,6 +590,102 @@ mod tests {
         })
     }
 
+    fn number_eq(src: &str, number: Number, suffix: Option<PrimitiveType>) {
+        parse_test(src, |p| {
+            assert_eq!(
+                p.parse::<NumberLiteral>()?,
+                NumberLiteral { number, suffix }
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn number_radix() {
+        number_eq("0x1bf52", Number::Digit(0x1bf52), None);
+        number_eq("0b1010_1010", Number::Digit(0b1010_1010), None);
+        number_eq("0o777", Number::Digit(0o777), None);
+        number_eq("0xffu8", Number::Digit(0xff), Some(PrimitiveType::U8));
+    }
+
+    #[test]
+    fn number_wide() {
+        number_eq(
+            "340282366920938463463374607431768211455",
+            Number::Digit(u128::MAX),
+            None,
+        );
+    }
+
+    #[test]
+    fn number_exact_fraction() {
+        number_eq("1f05", Number::Float(1.05), None);
+        number_eq("1_000f000_1", Number::Float(1000.0001), None);
+    }
+
+    #[test]
+    fn number_exponent() {
+        number_eq("1f5e3", Number::Float(1.5e3), None);
+        number_eq("15en1", Number::Float(15e-1), None);
+        number_eq("2e2_f32", Number::Float(2e2), Some(PrimitiveType::F32));
+    }
+
+    #[test]
+    fn number_suffix() {
+        number_eq(
+            "114514i128",
+            Number::Digit(114514),
+            Some(PrimitiveType::I128),
+        );
+        number_eq("1_f32", Number::Float(1.0), Some(PrimitiveType::F32));
+        number_eq("1f32", Number::Float(1.32), None);
+        number_eq("1f5_f64", Number::Float(1.5), Some(PrimitiveType::F64));
+    }
+
+    #[test]
+    #[should_panic]
+    fn number_bad_suffix() {
+        parse_test("1f5_i32", |p| {
+            p.parse::<NumberLiteral>()?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn number_bad_digit() {
+        parse_test("0o778", |p| {
+            p.parse::<NumberLiteral>()?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn bool() {
+        parse_test("zhen1 jia3", |p| {
+            assert!(p.parse::<BoolLiteral>()?.parsed);
+            assert!(!p.parse::<BoolLiteral>()?.parsed);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn null() {
+        parse_test("kong1zhi3", |p| {
+            p.parse::<NullLiteral>()?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn literal_is_not_ident() {
+        parse_test("zhen1", |p| {
+            p.parse::<Ident>()?;
+            Ok(())
+        })
+    }
+
     #[test]
     fn function_call() {
         parse_test("ya1 1919810 fen1 chuan4 acminoac ru4 han2shu4", |p| {
@@ -476,6 +719,100 @@ mod tests {
         })
     }
 
+    /// write the expression in prefix notation, like `(jia1 1 (cheng2 2 3))`
+    fn prefix(expr: &Expr) -> String {
+        match expr {
+            Expr::Atomic(atomic) => match &**atomic {
+                AtomicExpr::NumberLiteral(NumberLiteral {
+                    number: Number::Digit(n),
+                    ..
+                }) => n.to_string(),
+                AtomicExpr::Variable(name) => name.to_string(),
+                atomic => panic!("unexpected atomic {atomic:?}"),
+            },
+            Expr::Unary { op, val } => format!("({} {})", **op, prefix(val)),
+            Expr::Binary { op, l, r } => format!("({} {} {})", **op, prefix(l), prefix(r)),
+            Expr::Conditional {
+                conds, then, else_, ..
+            } => {
+                let conds = conds.iter().map(prefix).collect::<Vec<_>>().join(" ");
+                format!("(ruo4 ({conds}) {} {})", prefix(then), prefix(else_))
+            }
+        }
+    }
+
+    fn expr_eq(src: &str, expect: &str) {
+        parse_test(src, |p| {
+            assert_eq!(prefix(&p.parse::<Expr>()?), expect);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn expr_tree() {
+        expr_eq("1 jia1 2 cheng2 3", "(jia1 1 (cheng2 2 3))");
+        expr_eq("jie2 1 jia1 2 he2 cheng2 3", "(cheng2 (jia1 1 2) 3)");
+        expr_eq("fu4 a mi4 b", "(mi4 (fu4 a) b)");
+        expr_eq("fei1 a tong2 b yu3 c", "(yu3 (tong2 (fei1 a) b) c)");
+    }
+
+    #[test]
+    fn associativity() {
+        expr_eq("1 jian3 2 jian3 3", "(jian3 (jian3 1 2) 3)");
+        expr_eq("2 mi4 3 mi4 2", "(mi4 2 (mi4 3 2))");
+        expr_eq("2 mi4 3 cheng2 2", "(cheng2 (mi4 2 3) 2)");
+    }
+
+    #[test]
+    fn conditional() {
+        expr_eq(
+            "ruo4 can1 a da4 b jie2 a ze2 b jia1 1",
+            "(ruo4 ((da4 a b)) a (jia1 b 1))",
+        );
+        expr_eq(
+            "2 cheng2 jie2 ruo4 can1 x fen1 c jie2 a ze2 b he2",
+            "(cheng2 2 (ruo4 (x c) a b))",
+        );
+        // else-if chains are nested conditional expressions
+        expr_eq(
+            "ruo4 can1 a jie2 1 ze2 ruo4 can1 b jie2 2 ze2 3",
+            "(ruo4 (a) 1 (ruo4 (b) 2 3))",
+        );
+        parse_test("ruo4 can1 a jie2 b ze2 c", |p| {
+            assert_eq!(p.parse::<Expr>()?.get_span(), Span::new(0, 7));
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn conditional_without_else() {
+        parse_test("ruo4 can1 a jie2 b", |p| {
+            p.parse::<Expr>()?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn expr_span() {
+        parse_test("a jia1 b cheng2 c", |p| {
+            let Expr::Binary { r, .. } = p.parse::<Expr>()? else {
+                panic!("expect a binary expression")
+            };
+            assert_eq!(r.get_span(), Span::new(2, 5));
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn unclosed_bracket() {
+        parse_test("jie2 1 jia1 2 cheng2 3", |p| {
+            p.parse::<Expr>()?;
+            Ok(())
+        })
+    }
+
     #[test]
     fn complex_expr() {
         // 119 + 810 * 114514 - 12
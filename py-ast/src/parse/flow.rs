@@ -1,4 +1,5 @@
 use super::*;
+use crate::complex_pu;
 use py_lex::syntax::{ControlFlow, Symbol};
 
 #[derive(Debug, Clone)]
@@ -41,6 +42,18 @@ impl ParseUnit<Token> for Conditions {
     }
 }
 
+impl Conditions {
+    /// conditions of `ruo4`, which cant be empty
+    pub(crate) fn parse_if(p: &mut Parser<Token>) -> Result<Conditions> {
+        let conds = p.parse::<PU<Conditions>>()?;
+        if conds.is_empty() {
+            return conds
+                .throw("empty conditions are only allowed in `chong2`, `ruo4` needs a condition");
+        }
+        Ok(conds.take())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IfBranch {
     pub conds: Conditions,
@@ -52,7 +65,7 @@ impl ParseUnit<Token> for IfBranch {
 
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
         p.r#match(ControlFlow::If)?;
-        let conds = p.parse::<Conditions>()?;
+        let conds = Conditions::parse_if(p)?;
         let body = p.parse::<CodeBlock>().apply(mapper::MustMatch)?;
         Ok(IfBranch { conds, body })
     }
@@ -85,7 +98,7 @@ impl ParseUnit<Token> for ElseIfBranch {
         p.r#match(ControlFlow::Else)?;
         p.r#match(ControlFlow::If)?;
 
-        let conds = p.parse::<Conditions>()?;
+        let conds = Conditions::parse_if(p)?;
         let body = p.parse::<CodeBlock>().apply(mapper::MustMatch)?;
         Ok(IfBranch { conds, body })
     }
@@ -127,6 +140,47 @@ impl ParseUnit<Token> for While {
     }
 }
 
+complex_pu! {
+    cpu ForInit {
+        VarDefine,
+        VarLet,
+        VarStore
+    }
+}
+
+/// `xun2huan2 can1 $init fen1 $cond fen1 $step jie2 han2 ... jie2`
+///
+/// `$init` and `$step` could be omitted, like `xun2huan2 can1 fen1 i xiao3 n fen1 jie2`
+#[derive(Debug, Clone)]
+pub struct For {
+    pub init: Option<ForInit>,
+    pub cond: Expr,
+    pub step: Option<VarStore>,
+    pub block: CodeBlock,
+}
+
+impl ParseUnit<Token> for For {
+    type Target = For;
+
+    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
+        p.r#match(ControlFlow::For)?;
+        p.r#match(Symbol::Parameter).apply(mapper::MustMatch)?;
+        let init = p.parse::<ForInit>().apply(mapper::Try)?;
+        p.r#match(Symbol::Semicolon).apply(mapper::MustMatch)?;
+        let cond = p.parse::<Expr>().apply(mapper::MustMatch)?;
+        p.r#match(Symbol::Semicolon).apply(mapper::MustMatch)?;
+        let step = p.parse::<VarStore>().apply(mapper::Try)?;
+        p.r#match(Symbol::EndOfBlock).apply(mapper::MustMatch)?;
+        let block = p.parse::<CodeBlock>().apply(mapper::MustMatch)?;
+        Ok(For {
+            init,
+            cond,
+            step,
+            block,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Return {
     pub val: Option<Expr>,
@@ -164,6 +218,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn r#for() {
+        let src = "
+        xun2huan2 can1 zheng3 i wei2 0 fen1 i xiao3 5 fen1 i wei2 i jia1 1 jie2 han2
+            s wei2 s jia1 i fen1
+        jie2";
+        parse_test(src, |p| {
+            let for_ = p.parse::<For>()?;
+            assert!(matches!(for_.init, Some(ForInit::VarDefine(..))));
+            assert!(for_.step.is_some());
+            Ok(())
+        });
+
+        let src = "xun2huan2 can1 fen1 i xiao3 5 fen1 jie2 han2 jie2";
+        parse_test(src, |p| {
+            let for_ = p.parse::<For>()?;
+            assert!(for_.init.is_none() && for_.step.is_none());
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn for_without_condition() {
+        parse_test("xun2huan2 can1 i wei2 0 fen1 fen1 jie2 han2 jie2", |p| {
+            p.parse::<For>()?;
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn if_without_condition() {
+        parse_test("ruo4 can1 jie2 han2 jie2", |p| {
+            p.parse::<If>()?;
+            Ok(())
+        });
+    }
+
     #[test]
     fn r#while() {
         let src = "
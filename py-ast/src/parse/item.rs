@@ -1,66 +1,88 @@
 use super::*;
-use py_lex::syntax::Symbol;
-
-#[derive(Debug, Clone)]
-pub struct Comment;
-
-impl ParseUnit<Token> for Comment {
-    type Target = Comment;
-
-    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
-        p.r#match(Symbol::Comment)?;
-
-        loop {
-            let str = p
-                .parse::<Token>()
-                .map_err(|e| e.append("comment without ending"))?;
-
-            if &*str == "jie2" {
-                return Ok(Comment {});
-            }
-        }
-    }
-}
+use py_lex::syntax::{ControlFlow, Symbol};
 
 #[derive(Debug, Clone)]
 pub struct FnDefine {
     pub export: Option<Span>,
-    pub ty: types::TypeDefine,
+    /// [`None`] if the return type is `ling4`, it will be inferred from `fan3` statements
+    pub ty: Option<types::TypeDefine>,
     pub name: Ident,
     pub params: Parameters,
     pub codes: CodeBlock,
     pub retty_span: Span,
     pub sign_span: Span,
+    /// some statements in the function are [`Broken`]
+    pub broken: bool,
+    /// the signature is broken after the name, so only the name is known. parameters and
+    /// codes are empty, and `broken` is true
+    pub placeholder: bool,
+}
+
+/// the signature of a function before its parameters: `dao3chu1`, the return type and the name
+fn fn_head(
+    p: &mut Parser<Token>,
+) -> Result<(Option<Span>, Span, Option<types::TypeDefine>, Ident)> {
+    let export = p
+        .r#match(RPU(Symbol::Export))
+        .apply(mapper::Try)?
+        .map(|pu| pu.get_span());
+    // `ling4` means the return type is inferred
+    let (retty_span, ty) = match p.r#match(RPU(Symbol::Let)).apply(mapper::Try)? {
+        Some(let_) => (let_.get_span(), None),
+        None => {
+            let ty = p.parse::<PU<types::TypeDefine>>()?;
+            (ty.get_span(), Some(ty.take()))
+        }
+    };
+    let name = p.parse::<Ident>()?;
+    Ok((export, retty_span, ty, name))
+}
+
+impl FnDefine {
+    /// the placeholder of a broken function whose name could be parsed, so calls to it are
+    /// known to be calls to a broken function, rather than to an undefined one
+    fn placeholder(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
+        let (export, retty_span, ty, name) = fn_head(p)?;
+        Ok(Self {
+            export,
+            retty_span,
+            sign_span: p.get_span(),
+            ty,
+            name,
+            params: Parameters { params: vec![] },
+            codes: CodeBlock { stmts: vec![] },
+            broken: true,
+            placeholder: true,
+        })
+    }
 }
 
 impl ParseUnit<Token> for FnDefine {
     type Target = FnDefine;
 
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
-        let export = p
-            .r#match(RPU(Symbol::Export))
-            .apply(mapper::Try)?
-            .map(|pu| pu.get_span());
-        let ty = p.parse::<PU<types::TypeDefine>>()?;
-        let name = p.parse::<Ident>()?;
+        let (export, retty_span, ty, name) = fn_head(p)?;
         let params = p.parse::<PU<Parameters>>()?;
+        let reported = p.reported().len();
         let codes = p.parse::<CodeBlock>().apply(mapper::MustMatch)?;
 
         Ok(Self {
             export,
-            retty_span: ty.get_span(),
-            sign_span: ty.get_span().merge(params.get_span()),
-            ty: ty.take(),
+            retty_span,
+            sign_span: retty_span.merge(params.get_span()),
+            ty,
             name,
             params: params.take(),
             codes,
+            broken: p.reported().len() != reported,
+            placeholder: false,
         })
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct CodeBlock {
-    pub stmts: Vec<Statement>,
+    pub stmts: Vec<PU<Statement>>,
 }
 
 impl ParseUnit<Token> for CodeBlock {
@@ -69,17 +91,118 @@ impl ParseUnit<Token> for CodeBlock {
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
         p.r#match(Symbol::Block)?;
         let mut stmts = vec![];
-        while let Some(stmt) = p.parse::<Statement>().apply(mapper::Try)? {
-            stmts.push(stmt)
+        loop {
+            let error = match p.parse::<PU<Statement>>() {
+                Ok(stmt) => {
+                    stmts.push(stmt);
+                    continue;
+                }
+                Err(error) => error,
+            };
+            // the end of the block, or the block is never closed
+            let at_end = p.peek().is_none_or(|token| is(token, Symbol::EndOfBlock));
+            if at_end {
+                match error.kind() {
+                    ParseErrorKind::Unmatch => break,
+                    ParseErrorKind::Semantic => return Err(error),
+                }
+            }
+
+            p.report(error);
+            let span = p.once(skip_statement)?;
+            stmts.push(PU::new(span, Broken { span }.into()));
         }
         p.r#match(Symbol::EndOfBlock)?;
         Ok(Self { stmts })
     }
 }
 
+fn is(token: &Token, symbol: Symbol) -> bool {
+    **token == *symbol || symbol.aliases().contains(&&**token)
+}
+
+/// skip tokens of a broken statement, until the `fen1` which ends it, or the `jie2` which ends
+/// the block it starts(like `ruo4` and `chong2`)
+///
+/// the `jie2` which ends the current block is not skipped
+fn skip_statement(p: &mut Parser<Token>) -> Result<Span> {
+    // symbols which are ended with `jie2`
+    let mut opened = vec![];
+    while let Some(token) = p.peek() {
+        if opened.is_empty() && is(token, Symbol::EndOfBlock) {
+            break;
+        }
+
+        let token = p.next().unwrap();
+        if opened.is_empty() && is(token, Symbol::Semicolon) {
+            break;
+        }
+        if let Some(symbol) = [Symbol::Block, Symbol::Parameter]
+            .into_iter()
+            .find(|symbol| is(token, *symbol))
+        {
+            opened.push(symbol);
+        } else if is(token, Symbol::EndOfBlock) {
+            let ended = opened.pop();
+            // `ze2 han2` continues the statement
+            if opened.is_empty()
+                && ended == Some(Symbol::Block)
+                && p.r#match(ControlFlow::Else).is_err()
+            {
+                break;
+            }
+        }
+    }
+    Ok(p.get_span())
+}
+
+/// all items in a source file
+///
+/// if an item is broken, its error is reported to the parser, and tokens are skipped until the
+/// next function signature. a [`FnDefine`] placeholder is kept for it if its name is parsed
+#[derive(Debug, Clone, Copy)]
+pub struct Items;
+
+impl ParseUnit<Token> for Items {
+    type Target = Vec<Item>;
+
+    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
+        let mut items = vec![];
+        // skipping tokens of a broken item
+        let mut broken = false;
+        while p.peek().is_some() {
+            let item = if broken {
+                p.parse::<FnDefine>().map(Item::from)
+            } else {
+                p.parse::<Item>().apply(mapper::MustMatch)
+            };
+            match item {
+                Ok(item) => {
+                    items.push(item);
+                    broken = false;
+                }
+                Err(error) if broken && error.kind() == ParseErrorKind::Unmatch => {
+                    p.next();
+                }
+                Err(error) => {
+                    p.report(error);
+                    broken = true;
+                    match p.once(FnDefine::placeholder) {
+                        Ok(placeholder) => items.push(placeholder.into()),
+                        Err(_) => {
+                            p.next();
+                        }
+                    }
+                }
+            }
+        }
+        Ok(items)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::parse_test;
+    use crate::{parse_test, parse_test_with};
 
     use super::*;
 
@@ -124,18 +247,76 @@ mod tests {
     }
 
     #[test]
-    fn comment() {
-        parse_test("shi4 ehhhaaaaaaaaaaaaaaaaaaaaaaaa jie2", |p| {
-            p.parse::<Comment>()?;
+    fn function_define_with_inferred_return_type() {
+        parse_test(
+            "ling4 inc can1 zheng3 n jie2 han2 fan3 n jia1 1 fen1 jie2",
+            |p| {
+                let fn_define = p.parse::<FnDefine>()?;
+                assert!(fn_define.ty.is_none());
+                assert_eq!(&*fn_define.name, "inc");
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn hanzi_function_define() {
+        parse_test_with(
+            py_lex::LexMode::Unicode,
+            "整 主 参 整 甲 分 指 指 字 乙 结
+                    函
+                        若 参 甲 大 0 结
+                        函
+                            释 注释 结
+                        结 则 函
+                            返 甲 分
+                        结
+                    结",
+            |p| {
+                p.parse::<FnDefine>()?;
+                Ok(())
+            },
+        )
+    }
+
+    #[test]
+    fn keyword_with_wrong_tone() {
+        parse_test("zheng3 zhu3 can1 jie3 han2 jie2", |p| {
+            let error = p.parse::<FnDefine>().unwrap_err();
+            assert!(format!("{error:?}").contains("did you mean `jie2`?"));
             Ok(())
         });
     }
 
     #[test]
-    #[should_panic]
-    fn comment_without_ending() {
-        parse_test("shi4 ehhhaaaaaaaaaaaaaaaaaaaaaaaa ajie2", |p| {
-            p.parse::<CodeBlock>()?;
+    fn recover_from_broken_codes() {
+        let src = "
+        zheng3 a can1 jie2 han2
+            fan3 1 jia1 fen1
+            ruo4 can1 1 da4 jie2 han2 fan3 1 fen1 jie2 ze2 han2 fan3 0 fen1 jie2
+            fan3 2 fen1
+        jie2
+        zheng3 b can1 jie3 han2 jie2
+        zheng3 c can1 jie2 han2 fan3 3 fen1 jie2";
+        parse_test(src, |p| {
+            let items = p.parse::<Items>()?;
+            assert_eq!(p.take_reported().len(), 3);
+
+            let [Item::FnDefine(a), Item::FnDefine(b), Item::FnDefine(c)] = items.as_slice() else {
+                panic!("unexpected items: {items:?}")
+            };
+            assert!(a.broken && !c.broken);
+            // the signature of `b` is broken, only its name is kept
+            assert!(b.placeholder && &*b.name == "b");
+            let stmts = a.codes.stmts.iter().map(|stmt| &**stmt).collect::<Vec<_>>();
+            assert!(matches!(
+                stmts.as_slice(),
+                [
+                    Statement::Broken(..),
+                    Statement::Broken(..),
+                    Statement::Return(..)
+                ]
+            ));
             Ok(())
         });
     }
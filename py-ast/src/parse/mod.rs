@@ -45,6 +45,15 @@ impl ParseUnit<Token> for Ident {
 
         use py_lex::*;
 
+        // names of primitive types are identifiers, so they could be written without tones
+        if let Some(toneless) = &token.toneless {
+            if !types::PRIMITIVE_TYPES.contains(&&**token) {
+                let reason =
+                    format!("`{toneless}` is read as keyword `{token}`, it could not be ident");
+                return p.unmatch(reason);
+            }
+        }
+
         let keeps = &[
             ops::KEPPING_KEYWORDS,
             ops::sub_classes::KEPPING_KEYWORDS,
@@ -133,4 +142,26 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn toneless_ident() {
+        use terl::{Buffer, Message};
+
+        let options = py_lex::LexOptions {
+            tone_insensitive: true,
+            ..Default::default()
+        };
+        let source = Buffer::new("test.py1".to_owned(), "zheng wei".chars().collect());
+        let (_, mut p) = Parser::<char>::new(source)
+            .process(|p| py_lex::Tokens::parse_with(p, options))
+            .unwrap();
+
+        // primitive types are identifiers
+        assert!(&*p.parse::<Ident>().unwrap() == "zheng3");
+        let error = p.parse::<Ident>().unwrap_err().error();
+        assert!(matches!(
+            error.messages(),
+            [Message::Rich(reason, _), ..] if reason == "`wei` is read as keyword `wei2`, it could not be ident"
+        ));
+    }
 }
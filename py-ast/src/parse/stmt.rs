@@ -22,7 +22,7 @@ impl ParseUnit<Token> for VarAssign {
 #[derive(Debug, Clone)]
 pub struct VarDefine {
     pub ty: PU<types::TypeDefine>,
-    pub name: Ident,
+    pub name: PU<Ident>,
     pub init: Option<VarAssign>,
 }
 
@@ -31,15 +31,35 @@ impl ParseUnit<Token> for VarDefine {
 
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
         let ty = p.parse::<PU<types::TypeDefine>>()?;
-        let name = p.parse::<Ident>()?;
+        let name = p.parse::<PU<Ident>>()?;
         let init = p.parse::<VarAssign>().apply(mapper::Try)?;
         Ok(Self { ty, name, init })
     }
 }
 
+/// `ling4 $name wei2 $expr`, the type of the variable is inferred from the initial value
+#[derive(Debug, Clone)]
+pub struct VarLet {
+    pub name: PU<Ident>,
+    pub init: VarAssign,
+}
+
+impl ParseUnit<Token> for VarLet {
+    type Target = VarLet;
+
+    fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
+        p.r#match(Symbol::Let)?;
+        let name = p.parse::<PU<Ident>>().apply(mapper::MustMatch)?;
+        let init = p.parse::<VarAssign>().apply(mapper::MustMatch)?;
+        Ok(Self { name, init })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct VarStore {
     pub name: Ident,
+    /// the operator of compound assignments, like `jia1` in `x jia1 wei2 1`
+    pub op: Option<PU<py_lex::ops::Operators>>,
     pub assign: PU<VarAssign>,
 }
 
@@ -48,8 +68,16 @@ impl ParseUnit<Token> for VarStore {
 
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
         let name = p.parse::<Ident>()?;
+        let get_compound_op = |p: &mut Parser<Token>| {
+            p.parse::<PU<py_lex::ops::Operators>>()
+                .apply(mapper::Satisfy::new(
+                    |op: &PU<py_lex::ops::Operators>| op.is_compound_assignable(),
+                    |e| e.unmatch(""),
+                ))
+        };
+        let op = p.once(get_compound_op).apply(mapper::Try)?;
         let assign = p.parse::<PU<VarAssign>>()?;
-        Ok(VarStore { name, assign })
+        Ok(VarStore { name, op, assign })
     }
 }
 
@@ -72,7 +100,7 @@ impl ParseUnit<Token> for Parameter {
 
     fn parse(p: &mut Parser<Token>) -> ParseResult<Self, Token> {
         let ty = p.parse::<PU<types::TypeDefine>>()?;
-        let name = p.parse::<Ident>()?;
+        let name = p.parse::<PU<Ident>>()?;
         let inner = VarDefine {
             ty,
             name,
@@ -161,10 +189,19 @@ macro_rules! statement_wrapper {
 
 statement_wrapper! {
     VarDefine => VarDefineStmt,
+    VarLet => VarLetStmt,
     FnCall => FnCallStmt,
     VarStore => VarStoreStmt,
 }
 
+/// placeholder of a statement which could not be parsed
+///
+/// the error has been reported to the parser, and the tokens of the statement are skipped
+#[derive(Debug, Clone)]
+pub struct Broken {
+    pub span: Span,
+}
+
 /// be different from [`crate::complex_pu`], this version using box to make [`Statement`] enum smaller
 macro_rules! statements {
     (
@@ -174,7 +211,12 @@ macro_rules! statements {
             $(#[$v_metas:meta])*
             $variant:ident
         ),*
-    }) => {
+    }
+        $(
+            $(#[$p_metas:meta])*
+            placeholder $placeholder:ident
+        )?
+    ) => {
         #[derive(Debug, Clone)]
         $(#[$metas])*
         pub enum $enum_name {
@@ -182,8 +224,20 @@ macro_rules! statements {
                 $(#[$v_metas])*
                 $variant(Box<$variant>),
             )*
+            $(
+                $(#[$p_metas])*
+                $placeholder(Box<$placeholder>),
+            )?
         }
 
+        $(
+        impl From<$placeholder> for $enum_name {
+             fn from(v: $placeholder) -> $enum_name {
+                <$enum_name>::$placeholder(Box::new(v))
+            }
+        }
+        )?
+
         $(
         impl From<$variant> for $enum_name {
              fn from(v: $variant) -> $enum_name {
@@ -220,19 +274,22 @@ statements! {
 
         // $ty $name
         VarDefineStmt,
+        // ling4 $name = $expr
+        VarLetStmt,
         If,
         While,
+        For,
         Return,
-        Comment,
         CodeBlock
     }
+    // never parsed, only made by error recovery
+    placeholder Broken
 }
 
 complex_pu! {
     cpu Item {
         // $ty $name (...)
-        FnDefine,
-        Comment
+        FnDefine
     }
 }
 
@@ -263,6 +320,24 @@ mod tests {
         });
     }
 
+    #[test]
+    fn variable_let() {
+        parse_test("ling4 a wei2 b jia1 1 fen1", |p| {
+            let stmt = p.parse::<Statement>()?;
+            assert!(matches!(stmt, Statement::VarLetStmt(..)));
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn variable_let_without_init() {
+        parse_test("ling4 a fen1", |p| {
+            p.parse::<Statement>()?;
+            Ok(())
+        });
+    }
+
     #[test]
     fn variable_reassign() {
         parse_test("a wei2 114514 fen1", |p| {
@@ -274,4 +349,37 @@ mod tests {
             Ok(())
         });
     }
+
+    #[test]
+    fn compound_assign() {
+        parse_test("a jia1 wei2 1 fen1", |p| {
+            let Statement::VarStoreStmt(stmt) = p.parse::<Statement>()? else {
+                panic!("expect a `VarStore`")
+            };
+            assert_eq!(stmt.op.as_deref(), Some(&py_lex::ops::Operators::Add));
+            Ok(())
+        });
+        parse_test("a zuo3yi2 wei2 b cheng2 2 fen1", |p| {
+            p.parse::<VarStore>()?;
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn compound_assign_with_compare() {
+        parse_test("a da4 wei2 1", |p| {
+            p.parse::<VarStore>()?;
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn compound_assign_with_log() {
+        parse_test("a dui4 wei2 2", |p| {
+            p.parse::<VarStore>()?;
+            Ok(())
+        });
+    }
 }
@@ -168,16 +168,17 @@ impl TryFrom<&TypeDefine> for crate::ir::types::TypeDefine {
         /*
            int: sign, width
            float: width
+           bool: nothing
         */
 
-        if &*def.ty == "zheng3" {
+        let primitive = if matches!(&*def.ty, "zheng3" | "整") {
             // default to be i64
             let sign = def.sign.map(|pu| pu.sign).unwrap_or(true);
 
             let width = if let Some(width_extend) = def.width {
                 if !width_extend.width.is_power_of_two()
                     || *width_extend.width > 128
-                    || *width_extend.width < 64
+                    || *width_extend.width < 8
                 {
                     return Err(width_extend.make_error(format!(
                         "`zheng3` with width {} is not suppert now",
@@ -199,8 +200,8 @@ impl TryFrom<&TypeDefine> for crate::ir::types::TypeDefine {
                 128 => if sign { I128 } else { U128 },
                 _ => unreachable!(),
             };
-            return Ok(ty.into());
-        } else if &*def.ty == "fu2" {
+            Some(ty)
+        } else if matches!(&*def.ty, "fu2" | "浮") {
             // default to be f32
             if let Some(sign) = def.sign {
                 return Err(
@@ -225,27 +226,51 @@ impl TryFrom<&TypeDefine> for crate::ir::types::TypeDefine {
                 64 => PrimitiveType::F64,
                 _ => unreachable!(),
             };
-            return Ok(ty.into());
-        }
+            Some(ty)
+        } else if matches!(&*def.ty, "bu4" | "布") {
+            if let Some(sign) = def.sign {
+                return Err(
+                    sign.make_error("`bu4` type cant be decorated with `you3fu2` or `wu2fu2`")
+                );
+            }
+            if let Some(width) = def.width {
+                return Err(width.make_error("`bu4` type cant be decorated with `kuan1`"));
+            }
+            Some(PrimitiveType::Bool)
+        } else {
+            None
+        };
 
-        if let Some(sign) = def.sign {
-            return Err(sign.make_error(format!(
-                "type `{}` with `you3fu2` or `wu2fu2` is not supperted now",
-                def.ty
-            )));
-        }
-        if let Some(width) = def.width {
-            return Err(width.make_error(format!(
-                "type `{}` with `you3fu2` or `wu2fu2` is not supperted now",
-                def.ty
-            )));
+        if primitive.is_none() {
+            if let Some(sign) = def.sign {
+                return Err(sign.make_error(format!(
+                    "type `{}` with `you3fu2` or `wu2fu2` is not supperted now",
+                    def.ty
+                )));
+            }
+            if let Some(width) = def.width {
+                return Err(width.make_error(format!(
+                    "type `{}` with `you3fu2` or `wu2fu2` is not supperted now",
+                    def.ty
+                )));
+            }
         }
-        let ty = def.ty.to_string();
 
-        if def.const_.is_none() && def.decorators.is_empty() {
-            return Ok(ComplexType::no_decorators(ty).into());
+        // `she4` on primitive types is meaningless now, so it is ignored like before
+        match primitive {
+            Some(primitive) if def.decorators.is_empty() => return Ok(primitive.into()),
+            None if def.const_.is_none() && def.decorators.is_empty() => {
+                return Ok(ComplexType::no_decorators(def.ty.to_string()).into());
+            }
+            _ => {}
         }
 
+        // decorated primitive types are stored as complex types whose name is the primitive
+        let ty = match primitive {
+            Some(primitive) => primitive.to_string(),
+            None => def.ty.to_string(),
+        };
+
         use crate::ir::types::TypeDecorators::*;
 
         let mut decorators = vec![];
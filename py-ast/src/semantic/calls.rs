@@ -0,0 +1,134 @@
+use crate::parse;
+use py_lex::PU;
+use std::collections::HashSet;
+
+/// a function by its name and the number of its parameters
+///
+/// types of arguments are unknown before calls are generated, so overloads with the same key
+/// cant be told apart
+pub type FnKey<'f> = (&'f str, usize);
+
+/// the function defined by `fn_define`
+pub fn fn_key(fn_define: &parse::FnDefine) -> FnKey<'_> {
+    (&fn_define.name, fn_define.params.len())
+}
+
+/// functions which are called in codes, before overloads are picked
+///
+/// functions whose return types are inferred must be generated after the functions they call
+pub trait Calls {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>);
+}
+
+impl<C: Calls> Calls for [C] {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        self.iter().for_each(|item| item.calls(calls))
+    }
+}
+
+impl<C: Calls> Calls for Option<C> {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        if let Some(item) = self {
+            item.calls(calls)
+        }
+    }
+}
+
+impl<C: Calls> Calls for PU<C> {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        (**self).calls(calls)
+    }
+}
+
+impl Calls for parse::CodeBlock {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        self.stmts.calls(calls)
+    }
+}
+
+impl Calls for parse::Statement {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        match self {
+            parse::Statement::FnCallStmt(stmt) => stmt.calls(calls),
+            parse::Statement::VarStoreStmt(stmt) => stmt.calls(calls),
+            parse::Statement::VarDefineStmt(stmt) => stmt.init.calls(calls),
+            parse::Statement::VarLetStmt(stmt) => stmt.init.calls(calls),
+            parse::Statement::If(if_) => {
+                for branch in &if_.branches {
+                    branch.conds.calls(calls);
+                    branch.body.calls(calls);
+                }
+                if let Some(else_) = &if_.else_ {
+                    else_.block.calls(calls);
+                }
+            }
+            parse::Statement::While(while_) => {
+                while_.conds.calls(calls);
+                while_.block.calls(calls);
+            }
+            parse::Statement::For(for_) => {
+                match &for_.init {
+                    Some(parse::ForInit::VarDefine(init)) => init.init.calls(calls),
+                    Some(parse::ForInit::VarLet(init)) => init.init.calls(calls),
+                    Some(parse::ForInit::VarStore(init)) => init.calls(calls),
+                    None => {}
+                }
+                for_.cond.calls(calls);
+                for_.step.calls(calls);
+                for_.block.calls(calls);
+            }
+            parse::Statement::Return(return_) => return_.val.calls(calls),
+            parse::Statement::CodeBlock(block) => block.calls(calls),
+            parse::Statement::Broken(..) => {}
+        }
+    }
+}
+
+impl Calls for parse::VarAssign {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        self.val.calls(calls)
+    }
+}
+
+impl Calls for parse::VarStore {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        self.assign.calls(calls)
+    }
+}
+
+impl Calls for parse::Conditions {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        self.conds.calls(calls)
+    }
+}
+
+impl Calls for parse::FnCall {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        calls.insert((&self.fn_name, self.args.len()));
+        self.args.args.calls(calls);
+    }
+}
+
+impl Calls for parse::Expr {
+    fn calls<'c>(&'c self, calls: &mut HashSet<FnKey<'c>>) {
+        match self {
+            parse::Expr::Atomic(atomic) => match &**atomic {
+                parse::AtomicExpr::FnCall(fn_call) => fn_call.calls(calls),
+                parse::AtomicExpr::Array(array) => array.calls(calls),
+                _ => {}
+            },
+            parse::Expr::Unary { val, .. } => val.calls(calls),
+            parse::Expr::Binary { l, r, .. } => {
+                l.calls(calls);
+                r.calls(calls);
+            }
+            parse::Expr::Conditional {
+                conds, then, else_, ..
+            } => {
+                conds.calls(calls);
+                then.calls(calls);
+                else_.calls(calls);
+            }
+        }
+    }
+}
@@ -1,10 +1,14 @@
+use super::calls::{fn_key, FnKey};
+use super::lint::{Lint, Warnings};
 use super::mangle::Mangle;
 use super::*;
 use crate::parse;
 use either::Either;
 use py_declare::mir::IntoIR;
 use py_declare::*;
+use py_ir::cfg::{ConstBool, ControlFlowGraph};
 use py_lex::PU;
+use std::collections::{HashMap, HashSet};
 use terl::*;
 
 py_ir::custom_ir_variable!(pub IR<py_ir::value::Value>);
@@ -19,6 +23,8 @@ type Errors = Either<Error, Vec<Error>>;
 
 type ItemsGenerateResult = Result<Vec<Item>, Either<Vec<Error>, Vec<Vec<Error>>>>;
 
+type FnGenerateResult = Result<FnDefine, Vec<Error>>;
+
 #[derive(Debug, Clone)]
 struct Results<T, E> {
     inner: Result<Vec<T>, Vec<E>>,
@@ -86,21 +92,87 @@ impl<T, E> FromIterator<Result<T, E>> for Results<T, E> {
     }
 }
 
+fn fn_params(fn_define: &parse::FnDefine) -> Result<Vec<defs::Parameter>> {
+    fn_define.params.iter().try_fold(Vec::new(), |mut vec, pu| {
+        let name = pu.name.to_string();
+        let ty = pu.ty.to_mir_ty()?;
+        vec.push(defs::Parameter { name, ty });
+        Result::Ok(vec)
+    })
+}
+
+/// the return type is inferred from `fan3` statements if `ty` is [`None`]
+fn generate_fn(
+    defs: &Defs,
+    warnings: &Warnings,
+    fn_define: &parse::FnDefine,
+    mangled_name: String,
+    ty: Option<&py_ir::types::TypeDefine>,
+    params: Vec<defs::Parameter>,
+) -> FnGenerateResult {
+    // errors in a broken function have been reported while parsing, checking it again
+    // only makes misleading errors
+    if fn_define.broken {
+        return Err(vec![]);
+    }
+
+    let mut statement_transmuter = {
+        let scopes = BasicScopes::default();
+        let spans = fn_define.params.iter().map(WithSpan::get_span);
+        let retty_span = fn_define.retty_span;
+        let fn_scope = FnScope::new(&mangled_name, ty, retty_span, params.iter(), spans);
+        StatementGenerator::new(defs, fn_scope, scopes)
+    };
+
+    let body = statement_transmuter.generate(&fn_define.codes);
+    // variables are not used after where the error is, so they are only checked in a function
+    // which is fully generated
+    if body.is_ok() {
+        statement_transmuter.warn_unused_variables();
+    }
+    warnings.warn(std::mem::take(&mut statement_transmuter.warnings));
+    let body = match body {
+        Err(error) => Err(vec![error]),
+        // a function could end with an infinite loop, but never with a statement which may
+        // fall through
+        Ok(body) if ControlFlowGraph::new(&body).falls_through() => {
+            let reason = format!("function `{}` is never return", fn_define.name);
+            let error = fn_define.sign_span.make_error(reason);
+            Err(vec![error])
+        }
+
+        Ok(body) => Ok(body),
+    }?;
+
+    if !statement_transmuter.uninit_reads.is_empty() {
+        return Err(statement_transmuter.uninit_reads);
+    }
+
+    statement_transmuter.fn_scope.declare_map.declare_all()?;
+
+    let export = fn_define.export.is_some();
+    let mir_fn = mir::FnDefine {
+        export,
+        ty: statement_transmuter.fn_scope.ret,
+        body,
+        params,
+        name: mangled_name,
+    };
+    Ok(mir_fn.into_ir(&statement_transmuter.fn_scope.declare_map))
+}
+
+/// regist a function whose return type is given, and return the task to generate it
+///
+/// functions whose return types are inferred are generated by [`infer_fns`] instead
 fn fn_define_task<'d, M: Mangle>(
     define: &mut Defines<M>,
     fn_define: &'d parse::FnDefine,
-) -> Result<impl FnOnce(&'d Defines<M>) -> Result<FnDefine, Vec<Error>>, Error> {
-    let ty = fn_define.ty.to_mir_ty()?;
-
-    let params = fn_define
-        .params
-        .iter()
-        .try_fold(Vec::new(), |mut vec, pu| {
-            let name = pu.name.to_string();
-            let ty = pu.ty.to_mir_ty()?;
-            vec.push(defs::Parameter { name, ty });
-            Result::Ok(vec)
-        })?;
+) -> Result<Option<impl FnOnce(&'d Defines<M>) -> FnGenerateResult>, Error> {
+    let Some(ty) = &fn_define.ty else {
+        return Ok(None);
+    };
+    let ty = ty.to_mir_ty()?;
+    let params = fn_params(fn_define)?;
 
     let fn_sign = defs::FnSign::new(
         ty.clone(),
@@ -111,39 +183,285 @@ fn fn_define_task<'d, M: Mangle>(
 
     let mangled_name = define.regist_fn(fn_define, fn_sign)?;
 
-    Ok(|define: &Defines<M>| -> Result<FnDefine, Vec<Error>> {
-        let mut statement_transmuter = {
-            let scopes = BasicScopes::default();
-            let spans = fn_define.params.iter().map(WithSpan::get_span);
-            let fn_scope = FnScope::new(&mangled_name, params.iter(), spans);
-            StatementGenerator::new(&define.defs, fn_scope, scopes)
-        };
+    Ok(Some(move |define: &Defines<M>| {
+        let (defs, warnings) = (&define.defs, &define.warnings);
+        generate_fn(defs, warnings, fn_define, mangled_name, Some(&ty), params)
+    }))
+}
+
+/// generate a function whose return type is `ling4`, and regist it with the inferred type
+fn infer_fn<M: Mangle>(define: &mut Defines<M>, fn_define: &parse::FnDefine) -> FnGenerateResult {
+    let params = fn_params(fn_define).map_err(|error| vec![error])?;
+    let mangled_name = define.mangler.mangle_fn(&fn_define.name, &params);
+    let (defs, warnings) = (&define.defs, &define.warnings);
+    let fn_ = generate_fn(
+        defs,
+        warnings,
+        fn_define,
+        mangled_name,
+        None,
+        params.clone(),
+    )?;
+
+    let fn_sign = defs::FnSign::new(
+        fn_.ty.clone(),
+        params,
+        fn_define.retty_span,
+        fn_define.sign_span,
+    );
+    define
+        .regist_fn(fn_define, fn_sign)
+        .map_err(|error| vec![error])?;
+    Ok(fn_)
+}
+
+type IndexedResult = Result<(usize, Item), (usize, Vec<Error>)>;
+
+/// numbers of overloads of each function, and of overloads which failed to be generated
+type Overloads<'f> = HashMap<FnKey<'f>, usize>;
+
+/// generate functions whose return types are inferred, `fns` are functions with their indexes
+/// in items, and `overloads` are numbers of all defined overloads
+///
+/// a function is generated after the functions it calls, so the return types of callees are
+/// known. recursive functions cant be generated in this order, so their return types must be
+/// given. a function which calls a name with other overloads of its own parameter count is
+/// taken as calling them, rather than itself
+///
+/// # Return
+///
+/// results, and numbers of overloads which failed to be generated
+fn infer_fns<'f, M: Mangle>(
+    define: &mut Defines<M>,
+    fns: &[(usize, &'f parse::FnDefine)],
+    overloads: &Overloads<'f>,
+    broken: &HashSet<&str>,
+) -> (Vec<IndexedResult>, Overloads<'f>) {
+    use super::calls::Calls;
+
+    // indexes(in `fns`) of the functions which each function calls
+    let callees = (fns.iter().enumerate())
+        .map(|(caller, (_, fn_define))| {
+            let mut calls = HashSet::new();
+            fn_define.codes.calls(&mut calls);
+            (fns.iter().enumerate())
+                .filter(|(idx, (_, callee))| {
+                    let key = fn_key(callee);
+                    calls.contains(&key) && (*idx != caller || overloads[&key] == 1)
+                })
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = vec![];
+    let mut failed = Overloads::new();
+    let mut done = vec![false; fns.len()];
+    loop {
+        let ready = (0..fns.len())
+            .filter(|&idx| !done[idx] && callees[idx].iter().all(|&callee| done[callee]))
+            .collect::<Vec<_>>();
+        if ready.is_empty() {
+            break;
+        }
 
-        let body = match statement_transmuter.generate(&fn_define.codes) {
-            Err(error) => Err(vec![error]),
-            Ok(body) if !body.returned => {
-                let reason = format!("function `{}` is never return", fn_define.name);
-                let error = fn_define.sign_span.make_error(reason);
-                Err(vec![error])
+        for idx in ready {
+            done[idx] = true;
+            let (item_idx, fn_define) = fns[idx];
+            let result = if calls_failed(fn_define, overloads, &failed, broken) {
+                // errors have been reported in the callee
+                Err(vec![])
+            } else {
+                infer_fn(define, fn_define)
+            };
+            if result.is_err() {
+                *failed.entry(fn_key(fn_define)).or_default() += 1;
             }
+            results.push(
+                result
+                    .map(|fn_| (item_idx, fn_.into()))
+                    .map_err(|errors| (item_idx, errors)),
+            );
+        }
+    }
+
+    // functions which are left are recursive, or call recursive functions
+    for idx in (0..fns.len()).filter(|&idx| !done[idx]) {
+        let (item_idx, fn_define) = fns[idx];
+        *failed.entry(fn_key(fn_define)).or_default() += 1;
 
-            Ok(body) => Ok(body),
-        }?;
+        let mut visited = vec![false; fns.len()];
+        let mut stack = callees[idx].clone();
+        while let Some(callee) = stack.pop() {
+            if !std::mem::replace(&mut visited[callee], true) {
+                stack.extend(&callees[callee]);
+            }
+        }
+        if !visited[idx] {
+            // errors have been reported in the recursive function
+            results.push(Err((item_idx, vec![])));
+            continue;
+        }
 
-        statement_transmuter.fn_scope.declare_map.declare_all()?;
+        let reason = format!(
+            "cant infer the return type of `{}`, because it is recursive",
+            fn_define.name
+        );
+        let error = fn_define.sign_span.make_error(reason)
+            + format!("note: give `{}` a return type explicitly", fn_define.name);
+        results.push(Err((item_idx, vec![error])));
+    }
 
-        let export = fn_define.export.is_some();
-        let mir_fn = mir::FnDefine {
-            export,
-            ty,
-            body,
-            params,
-            name: mangled_name,
-        };
-        Ok(mir_fn.into_ir(&statement_transmuter.fn_scope.declare_map))
+    (results, failed)
+}
+
+/// functions which call functions failed to be generated are not generated, or there will be
+/// misleading errors about undefined functions
+///
+/// a call is failed if all overloads it could pick failed, or it could only call functions
+/// whose signatures are `broken`
+fn calls_failed(
+    fn_define: &parse::FnDefine,
+    overloads: &Overloads,
+    failed: &Overloads,
+    broken: &HashSet<&str>,
+) -> bool {
+    use super::calls::Calls;
+
+    let mut calls = HashSet::new();
+    fn_define.codes.calls(&mut calls);
+    calls.into_iter().any(|call| match overloads.get(&call) {
+        Some(overloads) => failed.get(&call) == Some(overloads),
+        // parameters of functions whose signatures are broken are unknown
+        None => broken.contains(call.0),
     })
 }
 
+/// functions are kept in the order in the source file
+fn in_order(results: Results<(usize, Item), (usize, Vec<Error>)>) -> ItemsGenerateResult {
+    match results.take() {
+        Ok(mut items) => {
+            items.sort_by_key(|(idx, _)| *idx);
+            Ok(items.into_iter().map(|(_, item)| item).collect())
+        }
+        Err(mut errors) => {
+            errors.sort_by_key(|(idx, _)| *idx);
+            Err(Either::Right(
+                errors.into_iter().map(|(_, errors)| errors).collect(),
+            ))
+        }
+    }
+}
+
+impl<M: Mangle> Defines<M> {
+    /// regist functions whose return types are given, and return tasks to generate them
+    #[allow(clippy::type_complexity)]
+    fn regist_items<'d>(
+        &mut self,
+        items: &'d [parse::Item],
+    ) -> Result<Vec<(usize, impl FnOnce(&'d Self) -> FnGenerateResult)>, Vec<Error>> {
+        let tasks = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| match item {
+                // errors in placeholders have been reported while parsing
+                parse::Item::FnDefine(fn_define) => !fn_define.placeholder,
+            })
+            .map(|(idx, item)| match item {
+                parse::Item::FnDefine(fn_define) => {
+                    fn_define_task(self, fn_define).map(|task| task.map(|task| (idx, task)))
+                }
+            })
+            .collect::<Results<_, _>>()
+            .take()?;
+        Ok(tasks.into_iter().flatten().collect())
+    }
+
+    /// generate functions whose return types are inferred
+    ///
+    /// # Return
+    ///
+    /// results, and indexes of items which should not be generated because they call functions
+    /// failed to be generated
+    fn infer_items(&mut self, items: &[parse::Item]) -> (Vec<IndexedResult>, HashSet<usize>) {
+        let fns = items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| match item {
+                parse::Item::FnDefine(fn_define)
+                    if fn_define.ty.is_none() && !fn_define.placeholder =>
+                {
+                    Some((idx, fn_define))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut overloads = Overloads::new();
+        let mut broken = HashSet::new();
+        for item in items {
+            match item {
+                parse::Item::FnDefine(fn_define) if fn_define.placeholder => {
+                    broken.insert(&*fn_define.name);
+                }
+                parse::Item::FnDefine(fn_define) => {
+                    *overloads.entry(fn_key(fn_define)).or_default() += 1
+                }
+            }
+        }
+        let (results, failed) = infer_fns(self, &fns, &overloads, &broken);
+
+        let skipped = (items.iter().enumerate())
+            .filter(|(_, item)| match item {
+                parse::Item::FnDefine(fn_define) => {
+                    calls_failed(fn_define, &overloads, &failed, &broken)
+                }
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        (results, skipped)
+    }
+
+    /// warn about functions which are not exported, and never called by exported functions
+    fn warn_unused_fns(&self, items: &[parse::Item]) {
+        use super::calls::Calls;
+
+        let fns = (items.iter())
+            .filter_map(|item| match item {
+                parse::Item::FnDefine(fn_define) => Some(fn_define).filter(|f| !f.placeholder),
+            })
+            .collect::<Vec<_>>();
+
+        // overloads are picked after calls are generated, so all overloads which could be
+        // called are used
+        let mut used = HashSet::new();
+        let mut stack = (fns.iter())
+            .filter(|fn_define| fn_define.export.is_some())
+            .map(|fn_define| fn_key(fn_define))
+            .collect::<Vec<_>>();
+        while let Some(key) = stack.pop() {
+            if !used.insert(key) {
+                continue;
+            }
+            for fn_define in fns.iter().filter(|fn_define| fn_key(fn_define) == key) {
+                let mut calls = HashSet::new();
+                fn_define.codes.calls(&mut calls);
+                stack.extend(calls);
+            }
+        }
+
+        let warnings = (fns.iter())
+            .filter(|fn_define| {
+                !used.contains(&fn_key(fn_define)) && !fn_define.name.starts_with('_')
+            })
+            .map(|fn_define| {
+                let reason = format!("function `{}` is never used", fn_define.name);
+                let warning = fn_define.sign_span.make_error(reason);
+                (Lint::UnusedFunctions, fn_define.sign_span, warning)
+            });
+        self.warnings.warn(warnings);
+    }
+}
+
 #[cfg(feature = "parallel")]
 mod parallel {
     use super::*;
@@ -178,21 +496,20 @@ mod parallel {
         type Forward = ItemsGenerateResult;
 
         fn generate(&mut self, items: &[parse::Item]) -> Self::Forward {
-            items
-                .iter()
-                .filter_map(|item| match item {
-                    parse::Item::FnDefine(fn_define) => Some(fn_define),
-                    parse::Item::Comment(_) => None,
-                })
-                .map(|fn_define| fn_define_task(self, fn_define).map(Box::new))
-                .collect::<Results<_, _>>()
-                .take()
-                .map_err(Either::Left)?
+            let tasks = self.regist_items(items).map_err(Either::Left)?;
+            self.warn_unused_fns(items);
+            let (inferred, skipped) = self.infer_items(items);
+            let mut results = tasks
                 .into_par_iter()
-                .map(|task| task(self).map(Into::into))
-                .collect::<Results<_, _>>()
-                .take()
-                .map_err(Either::Right)
+                .filter(|(idx, _)| !skipped.contains(idx))
+                .map(|(idx, task)| {
+                    task(self)
+                        .map(|fn_| (idx, fn_.into()))
+                        .map_err(|errors| (idx, errors))
+                })
+                .collect::<Results<_, _>>();
+            results.extend(inferred);
+            in_order(results)
         }
     }
 }
@@ -204,21 +521,20 @@ mod normal {
         type Forward = ItemsGenerateResult;
 
         fn generate(&mut self, items: &[parse::Item]) -> Self::Forward {
-            items
-                .iter()
-                .filter_map(|item| match item {
-                    parse::Item::FnDefine(fn_define) => Some(fn_define),
-                    parse::Item::Comment(_) => None,
-                })
-                .map(|fn_define| fn_define_task(self, fn_define).map(Box::new))
-                .collect::<Results<_, _>>()
-                .take()
-                .map_err(Either::Left)?
+            let tasks = self.regist_items(items).map_err(Either::Left)?;
+            self.warn_unused_fns(items);
+            let (inferred, skipped) = self.infer_items(items);
+            let mut results = tasks
                 .into_iter()
-                .map(|task| task(self).map(Into::into))
-                .collect::<Results<_, _>>()
-                .take()
-                .map_err(Either::Right)
+                .filter(|(idx, _)| !skipped.contains(idx))
+                .map(|(idx, task)| {
+                    task(self)
+                        .map(|fn_| (idx, fn_.into()))
+                        .map_err(|errors| (idx, errors))
+                })
+                .collect::<Results<_, _>>();
+            results.extend(inferred);
+            in_order(results)
         }
     }
 }
@@ -229,7 +545,6 @@ impl<M: Mangle> Generate<parse::Item> for Defines<M> {
     fn generate(&mut self, item: &parse::Item) -> Self::Forward {
         match item {
             parse::Item::FnDefine(fn_define) => self.generate(fn_define).map(Into::into).map(Some),
-            parse::Item::Comment(..) => Ok(None),
         }
     }
 }
@@ -238,7 +553,19 @@ impl<M: Mangle> Generate<parse::FnDefine> for Defines<M> {
     type Forward = Result<FnDefine, Errors>;
 
     fn generate(&mut self, fn_define: &parse::FnDefine) -> Self::Forward {
-        fn_define_task(self, fn_define).map_err(Either::Left)?(self).map_err(Either::Right)
+        match fn_define_task(self, fn_define).map_err(Either::Left)? {
+            Some(task) => task(self).map_err(Either::Right),
+            None => {
+                let overloads = [(fn_key(fn_define), 1)].into();
+                let (mut results, _) =
+                    infer_fns(self, &[(0, fn_define)], &overloads, &HashSet::new());
+                match results.pop() {
+                    Some(Ok((_, Item::FnDefine(fn_)))) => Ok(fn_),
+                    Some(Err((_, errors))) => Err(Either::Right(errors)),
+                    None => unreachable!(),
+                }
+            }
+        }
     }
 }
 
@@ -247,17 +574,69 @@ struct StatementGenerator<'w> {
     pub fn_scope: FnScope,
     pub scopes: BasicScopes,
     stmts: mir::Statements,
+    /// variables which may not have been stored at the current statement, a variable is
+    /// identified by the group of its type, which is unique for each definition
+    uninit: HashSet<GroupIdx>,
+    /// reads of variables in `uninit`, they are reported after the whole function is generated
+    uninit_reads: Vec<Error>,
+    /// if the end of statements generated in the current path could be reached, its false
+    /// after `fan3` or an infinite loop
+    reachable: bool,
+    /// how variables and parameters are used, by where they are defined
+    usages: HashMap<Span, VarUsage>,
+    /// warnings with where they are, they are reported even if the function has errors
+    warnings: Vec<(Lint, Span, Error)>,
 }
 
 struct VarDeineLoc(usize);
 
+/// how a variable or a parameter is used, for [`Lint::UnusedVariables`] and
+/// [`Lint::UnusedAssignments`]
+struct VarUsage {
+    name: String,
+    parameter: bool,
+    read: bool,
+    /// stored after it is defined
+    stored: bool,
+}
+
+impl VarUsage {
+    fn new(name: &str, parameter: bool) -> Self {
+        Self {
+            name: name.to_owned(),
+            parameter,
+            read: false,
+            stored: false,
+        }
+    }
+}
+
+/// undefined names which are keywords with wrong tones(like `ruo3` or `ruo` for `ruo4`)
+fn keyword_note(name: &str) -> Option<String> {
+    let toneless = py_lex::pinyin::toneless(name);
+    py_lex::keywords()
+        .into_iter()
+        .filter(|keyword| keyword.is_ascii() && py_lex::pinyin::toneless(keyword) == toneless)
+        .min()
+        .map(|keyword| format!("`{keyword}` is a keyword, did you mean it?"))
+}
+
 impl<'w> StatementGenerator<'w> {
     fn new(defs: &Defs, fn_scope: FnScope, scopes: BasicScopes) -> StatementGenerator<'_> {
+        let usages = fn_scope
+            .parameters()
+            .map(|(name, def)| (def.span, VarUsage::new(name, true)))
+            .collect();
         StatementGenerator {
             defs,
             fn_scope,
             scopes,
             stmts: Default::default(),
+            uninit: Default::default(),
+            uninit_reads: Default::default(),
+            reachable: true,
+            usages,
+            warnings: Default::default(),
         }
     }
 
@@ -278,7 +657,7 @@ impl<'w> StatementGenerator<'w> {
         let init = mir::Undeclared::new(init.into(), param_ty);
         let temp_name = self.fn_scope.temp_name();
         let loc = self.push_var_define(mir::VarDefine {
-            ty: param_ty,
+            ty: result_ty,
             name: temp_name.clone(),
             init: Some(init),
             is_temp: true,
@@ -311,12 +690,126 @@ impl<'w> StatementGenerator<'w> {
         std::mem::replace(&mut self.stmts, new)
     }
 
+    /// regist a variable in the current scope, warn if it shadows a variable in outer scopes
+    fn define_variable(&mut self, name: &PU<parse::Ident>, ty: GroupIdx) {
+        let span = name.get_span();
+        if let Some(outer) = self.scopes.search_outer_variable(name) {
+            let warning = span.make_error(format!("`{name}` shadows a variable in an outer scope"))
+                + outer.span.make_message(format!("`{name}` is defined here"));
+            self.warnings.push((Lint::ShadowedVariables, span, warning));
+        }
+        let def = defs::VarDef {
+            ty,
+            mutable: true,
+            span,
+        };
+        self.scopes.regist_variable(name, def);
+        self.usages.insert(span, VarUsage::new(name, false));
+    }
+
+    fn usage(&mut self, def: &defs::VarDef) -> &mut VarUsage {
+        self.usages
+            .get_mut(&def.span)
+            .expect("usages of all variables are tracked")
+    }
+
+    /// warn about variables and parameters which are never read
+    fn warn_unused_variables(&mut self) {
+        for (span, usage) in &self.usages {
+            // names started with `_` are unused on purpose
+            if usage.read || usage.name.starts_with('_') {
+                continue;
+            }
+            let name = &usage.name;
+            let (lint, reason) = match (usage.parameter, usage.stored) {
+                (true, _) => (Lint::UnusedVariables, format!("unused parameter `{name}`")),
+                (false, false) => (Lint::UnusedVariables, format!("unused variable `{name}`")),
+                (false, true) => (
+                    Lint::UnusedAssignments,
+                    format!("variable `{name}` is assigned to, but never read"),
+                ),
+            };
+            let warning = span.make_error(reason)
+                + format!("note: if this is intentional, prefix it with an underscore: `_{name}`");
+            self.warnings.push((lint, *span, warning));
+        }
+    }
+
     fn search_value(&mut self, name: &str) -> Option<defs::VarDef> {
         self.fn_scope
             .search_parameter(name)
             .or_else(|| self.scopes.search_variable(name))
     }
 
+    /// the "did you mean" note for an undefined variable
+    fn value_note(&self, name: &str) -> Option<String> {
+        let values = self
+            .fn_scope
+            .parameter_names()
+            .chain(self.scopes.variable_names());
+        py_lex::suggest::did_you_mean(name, values).or_else(|| keyword_note(name))
+    }
+
+    /// check if the variable has been stored before it is read at `at`
+    fn read_variable(&mut self, name: &str, def: &defs::VarDef, at: Span) {
+        // only the first read is reported, later reads are caused by the same missing store
+        if !self.uninit.remove(&def.ty) {
+            return;
+        }
+        let reason = format!("use of possibly uninitialized variable `{name}`");
+        let note = format!("`{name}` is defined here without a value");
+        let error = at.make_error(reason) + def.span.make_message(note);
+        self.uninit_reads.push(error);
+    }
+
+    /// run a path which may not be executed, like branches of `ruo4` and bodies of loops
+    ///
+    /// a path which is never `entered` because of constant conditions is still generated, but
+    /// warnings and reads of uninitialized variables in it are dropped
+    ///
+    /// # Return
+    ///
+    /// the result, and variables which may not have been stored at the end of the path, or
+    /// [`None`] if the end is never reached. `uninit` is restored, so other paths start from
+    /// the same state
+    fn in_new_path<R>(
+        &mut self,
+        entered: bool,
+        active: impl FnOnce(&mut Self) -> R,
+    ) -> (R, Option<HashSet<GroupIdx>>) {
+        let (reads, warnings) = (self.uninit_reads.len(), self.warnings.len());
+        let before = self.uninit.clone();
+        let reachable = std::mem::replace(&mut self.reachable, true);
+        let r = active(self);
+        let uninit = std::mem::replace(&mut self.uninit, before);
+        let end = std::mem::replace(&mut self.reachable, reachable);
+        if !entered {
+            self.uninit_reads.truncate(reads);
+            self.warnings.truncate(warnings);
+        }
+        (r, (entered && end).then_some(uninit))
+    }
+
+    /// generate statements which are never executed. errors in them are still reported, but
+    /// the statements, warnings and reads of uninitialized variables in them are dropped
+    fn in_dead_code<R>(&mut self, active: impl FnOnce(&mut Self) -> R) -> R {
+        let stmts = self.stmts.len();
+        let r = self.in_new_path(false, active).0;
+        self.stmts.truncate(stmts);
+        r
+    }
+
+    /// warn that `block` is never executed because of constant conditions
+    ///
+    /// blocks in it are not warned, they are in a path which is never entered
+    fn warn_dead_block(&mut self, block: &parse::CodeBlock) {
+        if let Some(first) = block.stmts.first() {
+            let warning = first.make_error("unreachable statement");
+            self.warnings
+                .push((Lint::UnreachableCode, first.get_span(), warning));
+        }
+    }
+
     fn in_new_basic_scope<R>(&mut self, active: impl FnOnce(&mut Self) -> R) -> R {
         self.scopes.push(Default::default());
         let r = active(self);
@@ -331,19 +824,25 @@ impl Generate<parse::Statement> for StatementGenerator<'_> {
     fn generate(&mut self, stmt: &parse::Statement) -> Self::Forward {
         match stmt {
             parse::Statement::VarStoreStmt(stmt) => self.generate(&****stmt).map(Into::into),
-            parse::Statement::If(stmt) => self.generate(&**stmt).map(Into::into),
-            parse::Statement::While(stmt) => self.generate(&**stmt).map(Into::into),
+            parse::Statement::If(stmt) => self.generate(&**stmt),
+            parse::Statement::While(stmt) => self.generate(&**stmt),
+            parse::Statement::For(stmt) => self.generate(&**stmt).map(Into::into),
             parse::Statement::Return(stmt) => self.generate(&**stmt).map(Into::into),
             parse::Statement::CodeBlock(stmt) => self.generate(&**stmt).map(Into::into),
             parse::Statement::VarDefineStmt(stmt) => match self.generate(&****stmt)? {
                 Some(var_define) => Ok(var_define.into()),
                 None => return Ok(None),
             },
+            parse::Statement::VarLetStmt(stmt) => match self.generate(&****stmt)? {
+                Some(var_define) => Ok(var_define.into()),
+                None => return Ok(None),
+            },
             parse::Statement::FnCallStmt(stmt) => {
                 self.generate(&****stmt)?;
                 return Ok(None);
             }
-            parse::Statement::Comment(..) => return Ok(None),
+            // the error has been reported while parsing
+            parse::Statement::Broken(..) => return Ok(None),
         }
         .map(Some)
     }
@@ -387,7 +886,10 @@ impl Generate<parse::FnCall> for StatementGenerator<'_> {
         })?;
 
         let Some(overloads) = self.defs.get_unmangled(&fn_call.fn_name) else {
-            return Err(fn_call.make_error(format!("call undefinded function {}", fn_call.fn_name)));
+            let error = fn_call.make_error(format!("call undefinded function {}", fn_call.fn_name));
+            let note = py_lex::suggest::did_you_mean(&fn_call.fn_name, self.defs.unmangled_names())
+                .or_else(|| keyword_note(&fn_call.fn_name));
+            return Err(note.into_iter().fold(error, Error::append));
         };
 
         let args_spans = fn_call
@@ -396,6 +898,15 @@ impl Generate<parse::FnCall> for StatementGenerator<'_> {
             .map(|pu| pu.get_span())
             .collect::<Vec<_>>();
 
+        // args like `kong1zhi3` could be any parameter type at the same position
+        for (nth, arg) in args.iter().enumerate() {
+            let candidates = overloads
+                .iter()
+                .filter_map(|overload| overload.params.get(nth))
+                .map(|param| Type::from(param.ty.clone()));
+            self.fn_scope.declare_map.instantiate(arg.ty, candidates);
+        }
+
         let overload_len_filter =
             filters::FnParamLen::new(Some(&fn_call.fn_name), args.len(), fn_call.get_span());
 
@@ -404,7 +915,7 @@ impl Generate<parse::FnCall> for StatementGenerator<'_> {
             if branch_builder.filter_self(self.defs, &overload_len_filter) {
                 // length of overload.params are equal to arg's
                 for ((param, arg), span) in overload.params.iter().zip(&args).zip(&args_spans) {
-                    let filter = filters::TypeEqual::new(&param.ty, *span);
+                    let filter = filters::ConvertibleTo::new(&param.ty, *span);
                     let declare_map = &mut self.fn_scope.declare_map;
                     branch_builder = branch_builder.new_depend::<Directly, _>(
                         declare_map,
@@ -437,7 +948,11 @@ impl Generate<parse::VarStore> for StatementGenerator<'_> {
         let val_at = var_store.assign.val.get_span();
 
         let Some(var_def) = self.search_value(&name) else {
-            return Err(val_at.make_error(format!("use of undefined variable {}", name)));
+            let error = val_at.make_error(format!("use of undefined variable {}", name));
+            return Err(self
+                .value_note(&name)
+                .into_iter()
+                .fold(error, Error::append));
         };
         if !var_def.mutable {
             return Err(val_at.make_error(format!("cant assign to a immmutable variable {}", name)));
@@ -447,7 +962,22 @@ impl Generate<parse::VarStore> for StatementGenerator<'_> {
             .declare_map
             .merge_group(val_at, var_def.ty, val.ty);
 
-        Ok(mir::VarStore { name, val })
+        // the variable is computed with the value in its own type
+        let compound = var_store.op.as_ref().map(|op| mir::Compound {
+            op: **op,
+            ty: var_def.ty,
+        });
+        if let Some(op) = &var_store.op {
+            self.read_variable(&name, &var_def, op.get_span());
+        }
+        self.uninit.remove(&var_def.ty);
+        // a compound assignment reads the variable only to store it again, its not a use
+        self.usage(&var_def).stored = true;
+        Ok(mir::VarStore {
+            name,
+            val,
+            compound,
+        })
     }
 }
 
@@ -460,23 +990,25 @@ impl Generate<parse::VarDefine> for StatementGenerator<'_> {
             .fn_scope
             .declare_map
             .new_static_group(var_define.ty.get_span(), std::iter::once(ty.into()));
-        self.scopes
-            .regist_variable(&var_define.name, defs::VarDef { ty, mutable: true });
+        self.define_variable(&var_define.name, ty);
 
         let init = match &var_define.init {
             Some(var_assign) => {
                 let init = self.generate(&var_assign.val)?;
+                let at = var_assign.val.get_span();
+                self.fn_scope.declare_map.merge_group(at, ty, init.ty);
 
                 if let Some(loc) = init.loc {
                     self.rename_var_define(loc, &var_define.name);
                     return Ok(None);
                 }
-                let at = var_assign.val.get_span();
-                self.fn_scope.declare_map.merge_group(at, ty, init.ty);
 
                 Some(mir::Undeclared::new(init.handle.val.into(), init.handle.ty))
             }
-            None => None,
+            None => {
+                self.uninit.insert(ty);
+                None
+            }
         };
 
         let name = var_define.name.to_string();
@@ -489,42 +1021,164 @@ impl Generate<parse::VarDefine> for StatementGenerator<'_> {
     }
 }
 
+impl Generate<parse::VarLet> for StatementGenerator<'_> {
+    type Forward = Result<Option<mir::VarDefine>>;
+
+    fn generate(&mut self, var_let: &parse::VarLet) -> Self::Forward {
+        // the variable is registered after the initial value is generated, so the initial value
+        // cant refer to the variable itself
+        let init = self.generate(&var_let.init.val)?;
+        // the variable shares the group of the initial value, so it will be declared as whatever
+        // the initial value is declared as
+        let ty = init.ty;
+        self.define_variable(&var_let.name, ty);
+
+        if let Some(loc) = init.loc {
+            self.rename_var_define(loc, &var_let.name);
+            return Ok(None);
+        }
+
+        Ok(Some(mir::VarDefine {
+            ty,
+            name: var_let.name.to_string(),
+            init: Some(mir::Undeclared::new(init.handle.val.into(), ty)),
+            is_temp: false,
+        }))
+    }
+}
+
 impl Generate<parse::If> for StatementGenerator<'_> {
-    type Forward = Result<mir::If>;
+    type Forward = Result<mir::Statement>;
 
     fn generate(&mut self, if_: &parse::If) -> Self::Forward {
-        let branches = if_
-            .branches
-            .iter()
-            .try_fold(vec![], |mut branches, branch| {
-                branches.push(self.generate(branch)?);
-                Ok(branches)
-            })?;
+        // variables which may not have been stored at the end of each path, paths which
+        // return or are never entered are not counted
+        let mut ends = vec![];
+        let mut branches = vec![];
+        // branches after one whose condition is always true are never tested
+        let mut tested = true;
+        for branch in &if_.branches {
+            // conditions are computed one by one until one of them is true
+            let cond = self.generate(&branch.conds)?;
+            let const_cond = cond.val.const_bool();
+            let entered = tested && const_cond != Some(false);
+            let (body, end) = self.in_new_path(entered, |g| g.generate(&branch.body));
+            if !entered {
+                self.warn_dead_block(&branch.body);
+            }
+            ends.extend(end);
+            tested &= const_cond != Some(true);
+            branches.push(mir::IfBranch { cond, body: body? });
+        }
         let else_ = match &if_.else_ {
-            Some(else_) => Some(self.generate(&else_.block)?),
+            Some(else_) => {
+                let (block, end) = self.in_new_path(tested, |g| g.generate(&else_.block));
+                if !tested {
+                    self.warn_dead_block(&else_.block);
+                }
+                ends.extend(end);
+                Some(block?)
+            }
+            None if tested => {
+                ends.push(self.uninit.clone());
+                None
+            }
             None => None,
         };
-        Ok(mir::If { branches, else_ })
+        self.reachable = !ends.is_empty();
+        self.uninit = ends.into_iter().flatten().collect();
+
+        Ok(mir::If { branches, else_ }.into())
     }
 }
 
 impl Generate<parse::While> for StatementGenerator<'_> {
-    type Forward = Result<mir::While>;
+    type Forward = Result<mir::Statement>;
 
     fn generate(&mut self, while_: &parse::While) -> Self::Forward {
-        let cond = self.generate(&while_.conds)?;
-        let body = self.generate(&while_.block)?;
-        Ok(mir::While { cond, body })
+        // `chong2 can1 jie2` loops forever
+        let cond = match while_.conds.is_empty() {
+            true => None,
+            false => Some(self.generate(&while_.conds)?),
+        };
+        let const_cond = match &cond {
+            Some(cond) => cond.val.const_bool(),
+            None => Some(true),
+        };
+        // the body may never be executed
+        let entered = const_cond != Some(false);
+        let body = self.in_new_path(entered, |g| g.generate(&while_.block)).0?;
+        if !entered {
+            self.warn_dead_block(&while_.block);
+        }
+        // there is no `break`, so a loop whose condition is always true never ends
+        self.reachable = const_cond != Some(true);
+
+        Ok(mir::While { cond, body }.into())
     }
 }
 
-impl Generate<parse::IfBranch> for StatementGenerator<'_> {
-    type Forward = Result<mir::IfBranch>;
+/// `for` is desugared into a block with `init` and a `while` in it:
+///
+/// ```text
+/// {
+///     $init
+///     while $cond {
+///         { $body }
+///         $step
+///     }
+/// }
+/// ```
+///
+/// the loop variable is only visible in the block, and the body is kept in its own block
+impl Generate<parse::For> for StatementGenerator<'_> {
+    type Forward = Result<mir::Statements>;
+
+    fn generate(&mut self, for_: &parse::For) -> Self::Forward {
+        self.in_new_basic_scope(|g| {
+            let outer = g.take_stmts();
+            match &for_.init {
+                Some(parse::ForInit::VarDefine(init)) => {
+                    if let Some(init) = g.generate(init)? {
+                        g.push_stmt(init);
+                    }
+                }
+                Some(parse::ForInit::VarLet(init)) => {
+                    if let Some(init) = g.generate(init)? {
+                        g.push_stmt(init);
+                    }
+                }
+                Some(parse::ForInit::VarStore(init)) => {
+                    let init = g.generate(init)?;
+                    g.push_stmt(init);
+                }
+                None => {}
+            }
 
-    fn generate(&mut self, branch: &parse::IfBranch) -> Self::Forward {
-        let cond = self.generate(&branch.conds)?;
-        let body = self.generate(&branch.body)?;
-        Ok(mir::IfBranch { cond, body })
+            let cond = g.generate_condition(std::slice::from_ref(&for_.cond))?;
+            let const_cond = cond.val.const_bool();
+            let entered = const_cond != Some(false);
+            let body = g.in_new_path(entered, |g| {
+                let mut body = mir::Statements::new();
+                body.push(g.generate(&for_.block)?);
+                if let Some(step) = &for_.step {
+                    let before_step = g.replace_stmts(body);
+                    let step = g.generate(step)?;
+                    g.push_stmt(step);
+                    body = g.replace_stmts(before_step);
+                }
+                Result::Ok(body)
+            });
+            let body = body.0?;
+            if !entered {
+                g.warn_dead_block(&for_.block);
+            }
+            g.reachable = const_cond != Some(true);
+
+            let cond = Some(cond);
+            g.push_stmt(mir::While { cond, body });
+            Ok(g.replace_stmts(outer))
+        })
     }
 }
 
@@ -535,14 +1189,19 @@ impl Generate<parse::Return> for StatementGenerator<'_> {
         let val = match &ret.val {
             Some(expr) => {
                 let val = self.generate(expr)?;
-                let mangled_fn = self.defs.get_mangled(&self.fn_scope.fn_name);
-                self.fn_scope
-                    .declare_map
-                    .declare_type(expr.get_span(), val.ty, &mangled_fn.ty);
+                let (map, ret) = (&mut self.fn_scope.declare_map, self.fn_scope.ret);
+                if map[ret].is_declared() {
+                    let ty = map.get_type(ret).clone();
+                    map.declare_type(expr.get_span(), val.ty, &ty);
+                } else {
+                    // the return type is inferred from all returned values
+                    map.merge_group(expr.get_span(), ret, val.ty);
+                }
                 Some(val.handle)
             }
             None => None,
         };
+        self.reachable = false;
         Ok(mir::Return { val })
     }
 }
@@ -553,10 +1212,24 @@ impl Generate<parse::CodeBlock> for StatementGenerator<'_> {
     fn generate(&mut self, item: &parse::CodeBlock) -> Self::Forward {
         self.in_new_basic_scope(|g| {
             let current_scope = g.take_stmts();
-            for stmt in &item.stmts {
-                if let Some(stmt) = g.generate(stmt)? {
+            let mut stmts = item.stmts.iter();
+            while let Some(stmt) = stmts.next() {
+                if let Some(stmt) = g.generate(&**stmt)? {
                     g.push_stmt(stmt);
                 }
+                if g.reachable {
+                    continue;
+                }
+
+                // statements after a `fan3` or an infinite loop are never executed
+                if let Some(next) = stmts.as_slice().first() {
+                    let warning = next.make_error("unreachable statement")
+                        + stmt.make_message("any code following this statement is unreachable");
+                    g.warnings
+                        .push((Lint::UnreachableCode, next.get_span(), warning));
+                }
+                g.in_dead_code(|g| stmts.try_for_each(|stmt| g.generate(&**stmt).map(drop)))?;
+                break;
             }
             Ok(g.replace_stmts(current_scope))
         })
@@ -567,12 +1240,24 @@ impl Generate<parse::Conditions> for StatementGenerator<'_> {
     type Forward = Result<mir::Condition>;
 
     fn generate(&mut self, conds: &parse::Conditions) -> Self::Forward {
+        self.generate_condition(conds)
+    }
+}
+
+impl StatementGenerator<'_> {
+    /// the value of the last expression is the condition, others are only computed
+    ///
+    /// `conds` should not be empty, empty conditions are rejected by the parser or handled
+    /// by the caller
+    fn generate_condition(&mut self, conds: &[parse::Expr]) -> Result<mir::Condition> {
         let (compute, val) = self.in_new_basic_scope(|g| {
+            // statements before the condition are not a part of it
+            let outer = g.take_stmts();
             let mut last_condition = g.generate(&conds[0])?;
             for arg in conds.iter().skip(1) {
                 last_condition = g.generate(arg)?;
             }
-            Ok((g.take_stmts(), last_condition.handle))
+            Ok((g.replace_stmts(outer), last_condition.handle))
         })?;
 
         // type check
@@ -589,47 +1274,76 @@ impl Generate<parse::Expr> for StatementGenerator<'_> {
     type Forward = Result<ValueHandle>;
 
     fn generate(&mut self, expr: &parse::Expr) -> Self::Forward {
-        let mut vals = Vec::new();
-        for item in expr.iter() {
-            match item {
-                parse::ExprItem::AtomicExpr(atomic) => vals.push(self.generate(atomic)?),
-                parse::ExprItem::Operators(op) => match op.associativity() {
-                    py_lex::ops::OperatorAssociativity::Binary => {
-                        let r = vals.pop().unwrap();
-                        let l = vals.pop().unwrap();
-                        self.fn_scope
-                            .declare_map
-                            .merge_group(expr.get_span(), l.ty, r.ty);
-                        use py_ir::types::PrimitiveType;
-                        use py_lex::ops::OperatorTypes::CompareOperator;
-
-                        // for compare operators(like == != < >), the result will be a boolean value,
-                        // not parameters' type
-                        let param_ty = l.ty;
-                        let result_ty = if op.op_ty() == CompareOperator {
-                            self.fn_scope
-                                .declare_map
-                                .new_static_group(expr.get_span(), [PrimitiveType::Bool.into()])
-                        } else {
-                            l.ty
-                        };
-
-                        let init = mir::Operate::Binary(**op, l.handle, r.handle);
-                        vals.push(self.temp_var_define(param_ty, result_ty, init));
+        match expr {
+            parse::Expr::Atomic(atomic) => self.generate(atomic),
+            parse::Expr::Unary { op, val } => {
+                let v = match &**val {
+                    // `fu4 128` fits in i8 while `128` doesnt
+                    parse::Expr::Atomic(atomic) if **op == py_lex::ops::Operators::Neg => {
+                        self.generate_atomic(atomic, true)?
                     }
-                    py_lex::ops::OperatorAssociativity::Unary => {
-                        let v = vals.pop().unwrap();
-                        let ty = v.ty;
+                    val => self.generate(val)?,
+                };
+                let ty = v.ty;
 
-                        let init = mir::Operate::Unary(**op, v.handle);
-                        vals.push(self.temp_var_define(ty, ty, init));
-                    }
+                let init = mir::Operate::Unary(**op, v.handle);
+                Ok(self.temp_var_define(ty, ty, init))
+            }
+            parse::Expr::Binary { op, l, r } => {
+                let l = self.generate(&**l)?;
+                let r = self.generate(&**r)?;
+                self.fn_scope
+                    .declare_map
+                    .merge_group(expr.get_span(), l.ty, r.ty);
+                use py_ir::types::PrimitiveType;
+                use py_lex::ops::OperatorTypes::CompareOperator;
+
+                // for compare operators(like == != < >), the result will be a boolean value,
+                // not parameters' type
+                let param_ty = l.ty;
+                let result_ty = if op.op_ty() == CompareOperator {
+                    self.fn_scope
+                        .declare_map
+                        .new_static_group(expr.get_span(), [PrimitiveType::Bool.into()])
+                } else {
+                    l.ty
+                };
+
+                let init = mir::Operate::Binary(**op, l.handle, r.handle);
+                Ok(self.temp_var_define(param_ty, result_ty, init))
+            }
+            parse::Expr::Conditional {
+                conds, then, else_, ..
+            } => {
+                let cond = self.generate(conds)?;
+                let (then, then_end) = self.in_new_path(true, |g| g.generate_arm(then));
+                let (else_, else_end) = self.in_new_path(true, |g| g.generate_arm(else_));
+                let (then, else_) = (then?, else_?);
+                self.uninit = then_end.into_iter().chain(else_end).flatten().collect();
+                let ty = then.val.ty;
+                self.fn_scope
+                    .declare_map
+                    .merge_group(expr.get_span(), ty, else_.val.ty);
 
-                    py_lex::ops::OperatorAssociativity::None => unreachable!(),
-                },
+                let init = mir::Select { cond, then, else_ };
+                Ok(self.temp_var_define(ty, ty, init))
             }
         }
-        vals.pop().ok_or_else(|| unreachable!())
+    }
+}
+
+impl StatementGenerator<'_> {
+    /// an arm of a conditional expression, the computing of it is kept in the arm because it
+    /// should only be executed when the arm is chosen
+    fn generate_arm(&mut self, expr: &parse::Expr) -> Result<mir::SelectArm> {
+        self.in_new_basic_scope(|g| {
+            let outer = g.take_stmts();
+            let val = g.generate(expr)?.handle;
+            Ok(mir::SelectArm {
+                val,
+                compute: g.replace_stmts(outer),
+            })
+        })
     }
 }
 
@@ -637,25 +1351,53 @@ impl Generate<PU<parse::AtomicExpr>> for StatementGenerator<'_> {
     type Forward = Result<ValueHandle>;
 
     fn generate(&mut self, atomic: &PU<parse::AtomicExpr>) -> Self::Forward {
+        self.generate_atomic(atomic, false)
+    }
+}
+
+impl StatementGenerator<'_> {
+    /// `negative` means the atomic is negated right after, so the range of integer literals
+    /// can be checked correctly
+    fn generate_atomic(
+        &mut self,
+        atomic: &PU<parse::AtomicExpr>,
+        negative: bool,
+    ) -> Result<ValueHandle> {
         let literal = match &**atomic {
             // atomics
             // 解析
             parse::AtomicExpr::CharLiteral(char) => py_ir::value::Literal::Char(char.parsed),
-            parse::AtomicExpr::NumberLiteral(n) => match n {
-                parse::NumberLiteral::Float(number) => py_ir::value::Literal::Float(*number),
-                parse::NumberLiteral::Digit(number) => py_ir::value::Literal::Integer(*number),
+            parse::AtomicExpr::BoolLiteral(bool) => py_ir::value::Literal::Bool(bool.parsed),
+            parse::AtomicExpr::NullLiteral(_) => {
+                // null could be any pointer, so its type is left to where it is used
+                let placeholder = Placeholder {
+                    expect: "a pointer",
+                    accept: py_ir::types::TypeDefine::is_pointer,
+                };
+                let ty = self
+                    .fn_scope
+                    .declare_map
+                    .new_placeholder_group(atomic.get_span(), placeholder);
+                let val = py_ir::value::Literal::Null.into();
+                return Ok(mir::Undeclared::new(val, ty).into());
+            }
+            parse::AtomicExpr::NumberLiteral(n) => match n.number {
+                parse::Number::Float(number) => py_ir::value::Literal::Float(number),
+                parse::Number::Digit(number) => py_ir::value::Literal::Integer(number),
             },
 
-            parse::AtomicExpr::StringLiteral(_str) => {
-                // TODO: init for array
-                todo!("a VarDefine statement will be generate...")
+            parse::AtomicExpr::StringLiteral(str) => {
+                py_ir::value::Literal::String(str.parsed.clone())
             }
             parse::AtomicExpr::FnCall(fn_call) => return self.generate(fn_call),
             parse::AtomicExpr::Variable(name) => {
                 let Some(def) = self.search_value(name) else {
-                    return Err(atomic.make_error("use of undefined variable"));
+                    let error = atomic.make_error("use of undefined variable");
+                    return Err(self.value_note(name).into_iter().fold(error, Error::append));
                 };
 
+                self.read_variable(name, &def, atomic.get_span());
+                self.usage(&def).read = true;
                 let val = mir::Value::Variable(name.to_string());
                 return Ok(mir::Undeclared::new(val, def.ty).into());
             }
@@ -666,9 +1408,283 @@ impl Generate<PU<parse::AtomicExpr>> for StatementGenerator<'_> {
         };
 
         let ty = self.fn_scope.declare_map.build_group({
-            let branches = mir::Undeclared::literal_branches(&literal);
-            GroupBuilder::new(atomic.get_span(), branches)
+            let branches = mir::Undeclared::literal_branches(&literal, negative);
+            let default = mir::Undeclared::literal_default(&literal);
+            GroupBuilder::new(atomic.get_span(), branches).with_default(default)
         });
+        // number with suffix like `114514u32`
+        if let parse::AtomicExpr::NumberLiteral(parse::NumberLiteral {
+            suffix: Some(suffix),
+            ..
+        }) = &**atomic
+        {
+            let suffix = (*suffix).into();
+            self.fn_scope
+                .declare_map
+                .declare_type(atomic.get_span(), ty, &suffix);
+        }
         Ok(mir::Undeclared::new(literal.into(), ty).into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::types::{PrimitiveType, TypeDefine};
+
+    fn generate_items(src: &str) -> ItemsGenerateResult {
+        let mut result = None;
+        crate::parse_test(src, |p| {
+            let items = p.parse::<parse::Items>()?;
+            let mut defines = Defines::<mangle::DefaultMangler>::default();
+            result = Some(defines.generate(&items[..]));
+            Ok(())
+        });
+        result.unwrap()
+    }
+
+    /// reasons of errors of each function which failed to be generated
+    fn fn_errors(src: &str) -> Vec<Vec<String>> {
+        let Err(Either::Right(errors)) = generate_items(src) else {
+            panic!("expect errors in functions");
+        };
+        (errors.into_iter())
+            .map(|errors| {
+                (errors.iter())
+                    .map(|error| match error.messages() {
+                        [Message::Rich(reason, _), ..] | [Message::Text(reason), ..] => {
+                            reason.clone()
+                        }
+                        _ => String::new(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// where the only error in the function is, and where its note is
+    fn uninit_read(src: &str) -> (Span, Span) {
+        let Err(Either::Right(errors)) = generate_items(src) else {
+            panic!("expect errors in functions");
+        };
+        let [errors] = &errors[..] else {
+            panic!("{errors:?}")
+        };
+        match errors[..] {
+            [ref error] => match error.messages() {
+                [Message::Rich(reason, at), Message::Rich(note, defined_at)] => {
+                    assert!(reason.starts_with("use of possibly uninitialized variable `x`"));
+                    assert_eq!(note, "`x` is defined here without a value");
+                    (*at, *defined_at)
+                }
+                messages => panic!("{messages:?}"),
+            },
+            _ => panic!("{errors:?}"),
+        }
+    }
+
+    /// names and types of variables defined in the function
+    fn var_types(src: &str) -> Vec<(String, TypeDefine)> {
+        let mut types = vec![];
+        crate::parse_test(src, |p| {
+            let items = p.parse::<parse::Items>()?;
+            let mut defines = Defines::<mangle::DefaultMangler>::default();
+            let items = defines
+                .generate(&items[..])
+                .unwrap_or_else(|errors| panic!("{errors:?}"));
+            let Item::FnDefine(fn_define) = &items[0];
+            for stmt in &fn_define.body.stmts {
+                if let py_ir::Statement::VarDefine(define) = stmt {
+                    types.push((define.name.clone(), define.ty.clone()));
+                }
+            }
+            Ok(())
+        });
+        types
+    }
+
+    #[test]
+    fn compare_temp_type() {
+        let src = "
+        bu4 gt can1 zheng3 x fen1 zheng3 y jie2
+        han2
+            fan3 x jia1 1 da4 y fen1
+        jie2";
+        // the sum is an integer, but the temp which stores the comparing is a bool
+        let types = var_types(src);
+        let (_, ty) = types.last().unwrap();
+        assert_eq!(ty, &TypeDefine::from(PrimitiveType::Bool));
+    }
+
+    #[test]
+    fn explicit_type_of_renamed_temp() {
+        let src = "
+        zheng3 f can1 jie2
+        han2
+            kuan1 8 zheng3 y wei2 fu4 1 fen1
+            fan3 0 fen1
+        jie2";
+        // the temp which stores `fu4 1` is renamed to `y`, and it has the type of `y`
+        let types = var_types(src);
+        assert_eq!(types, [("y".to_owned(), PrimitiveType::I8.into())]);
+    }
+
+    #[test]
+    fn call_broken_function() {
+        let src = "
+        zheng3 broken can1 zheng3 jie2
+        han2
+            fan3 1 fen1
+        jie2
+        dao3chu1 zheng3 caller can1 jie2
+        han2
+            fan3 ya1 1 ru4 broken fen1
+        jie2";
+        let source = Buffer::new("test.py1".to_owned(), src.chars().collect());
+        let (_, mut p) = Parser::<char>::new(source)
+            .process(|p| py_lex::Tokens::parse_with(p, Default::default()))
+            .unwrap();
+        let items = p.parse::<parse::Items>().unwrap();
+        assert_eq!(p.take_reported().len(), 1);
+        let parse::Item::FnDefine(placeholder) = &items[0];
+        assert!(placeholder.placeholder && &*placeholder.name == "broken");
+
+        // `caller` is not generated, there is no error about calling an undefined function
+        let mut defines = Defines::<mangle::DefaultMangler>::default();
+        let generated = defines.generate(&items[..]);
+        assert!(generated.is_ok_and(|items| items.is_empty()));
+    }
+
+    #[test]
+    fn infer_overloads() {
+        let src = "
+        ling4 f can1 kuan1 32 zheng3 x jie2
+        han2
+            zheng3 y wei2 1 fen1
+            fan3 ya1 y ru4 f fen1
+        jie2
+        ling4 f can1 zheng3 x jie2
+        han2
+            fan3 x fen1
+        jie2";
+        // `f(i32)` calls `f(i64)`, they are not recursive
+        let items = generate_items(src).unwrap_or_else(|errors| panic!("{errors:?}"));
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn failed_overload() {
+        let src = "
+        ling4 f can1 zheng3 x jie2
+        han2
+            fan3 y fen1
+        jie2
+        zheng3 f can1 kuan1 32 zheng3 x jie2
+        han2
+            fan3 1 fen1
+        jie2
+        zheng3 g can1 jie2
+        han2
+            kuan1 32 zheng3 a wei2 1 fen1
+            ya1 a ru4 f fen1
+            fan3 z fen1
+        jie2";
+        // `g` could call the other `f`, so its generated and its own errors are reported
+        let errors = fn_errors(src);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[1], ["use of undefined variable"]);
+    }
+
+    #[test]
+    fn recursive_inferred_fn() {
+        let src = "
+        ling4 f can1 zheng3 x jie2
+        han2
+            fan3 ya1 x ru4 f fen1
+        jie2
+        ling4 g can1 jie2
+        han2
+            fan3 ya1 1 ru4 f fen1
+        jie2
+        zheng3 h can1 jie2
+        han2
+            fan3 ya1 ru4 g fen1
+        jie2";
+        // `g` and `h` are skipped without errors of their own
+        let errors = fn_errors(src).concat();
+        assert_eq!(
+            errors,
+            ["cant infer the return type of `f`, because it is recursive"]
+        );
+    }
+
+    // in the sources below, `x` is defined at the 8th token(`@8..9`) of the function
+
+    #[test]
+    fn store_in_one_branch() {
+        let src = "
+        zheng3 f can1 bu4 c jie2
+        han2
+            zheng3 x fen1
+            ruo4 can1 c jie2 han2
+                x wei2 1 fen1
+            jie2
+            fan3 x fen1
+        jie2";
+        assert_eq!(uninit_read(src), (Span::new(21, 22), Span::new(8, 9)));
+    }
+
+    #[test]
+    fn store_in_loop() {
+        let src = "
+        zheng3 f can1 bu4 c jie2
+        han2
+            zheng3 x fen1
+            chong2 can1 c jie2 han2
+                x wei2 1 fen1
+            jie2
+            fan3 x fen1
+        jie2";
+        assert_eq!(uninit_read(src), (Span::new(21, 22), Span::new(8, 9)));
+    }
+
+    #[test]
+    fn compound_store_uninit() {
+        let src = "
+        zheng3 f can1 bu4 c jie2
+        han2
+            zheng3 x fen1
+            x jia1 wei2 1 fen1
+            fan3 x fen1
+        jie2";
+        // the compound operator reads `x`
+        assert_eq!(uninit_read(src), (Span::new(11, 12), Span::new(8, 9)));
+    }
+
+    #[test]
+    fn read_in_conditional_arm() {
+        // stored in one branch of `ruo4`
+        let src = "
+        zheng3 f can1 bu4 c jie2
+        han2
+            zheng3 x fen1
+            ruo4 can1 c jie2 han2
+                x wei2 1 fen1
+            jie2
+            fan3 ruo4 can1 c jie2 x ze2 0 fen1
+        jie2";
+        assert_eq!(uninit_read(src), (Span::new(25, 26), Span::new(8, 9)));
+
+        // stored in the body of `chong2`
+        let src = "
+        zheng3 f can1 bu4 c jie2
+        han2
+            zheng3 x fen1
+            chong2 can1 c jie2 han2
+                x wei2 1 fen1
+            jie2
+            fan3 ruo4 can1 c jie2 0 ze2 x fen1
+        jie2";
+        assert_eq!(uninit_read(src), (Span::new(27, 28), Span::new(8, 9)));
+    }
+}
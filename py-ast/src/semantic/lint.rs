@@ -0,0 +1,271 @@
+use std::collections::{HashMap, HashSet};
+use terl::*;
+
+/// checks which find codes that are legal but likely wrong, each of them could be allowed,
+/// warned or denied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// statements after `fan3` or infinite loops, and branches whose conditions are constant
+    UnreachableCode,
+    /// variables and parameters which are never used
+    UnusedVariables,
+    /// variables which are stored, but never read
+    UnusedAssignments,
+    /// functions which are not exported, and never called by exported functions
+    UnusedFunctions,
+    /// variables which have the same names as variables in outer scopes
+    ShadowedVariables,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 5] = [
+        Lint::UnreachableCode,
+        Lint::UnusedVariables,
+        Lint::UnusedAssignments,
+        Lint::UnusedFunctions,
+        Lint::ShadowedVariables,
+    ];
+
+    /// the name used in command lines, like `unused-variables`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnreachableCode => "unreachable-code",
+            Lint::UnusedVariables => "unused-variables",
+            Lint::UnusedAssignments => "unused-assignments",
+            Lint::UnusedFunctions => "unused-functions",
+            Lint::ShadowedVariables => "shadowed-variables",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Lint> {
+        Self::ALL.into_iter().find(|lint| lint.name() == name)
+    }
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// what to do when a [`Lint`] finds something
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// ignore it
+    Allow,
+    /// report it as a warning
+    Warn,
+    /// report it as an error, so the compilation fails
+    Deny,
+}
+
+impl Level {
+    /// the flag which sets the level, like `-W`
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Level::Allow => "-A",
+            Level::Warn => "-W",
+            Level::Deny => "-D",
+        }
+    }
+}
+
+/// levels of all lints, every lint is [`Level::Warn`] by default
+#[derive(Debug, Clone, Default)]
+pub struct Lints {
+    levels: HashMap<Lint, Level>,
+}
+
+impl Lints {
+    pub fn set(&mut self, lint: Lint, level: Level) {
+        self.levels.insert(lint, level);
+    }
+
+    pub fn level(&self, lint: Lint) -> Level {
+        self.levels.get(&lint).copied().unwrap_or(Level::Warn)
+    }
+
+    fn is_default(&self, lint: Lint) -> bool {
+        !self.levels.contains_key(&lint)
+    }
+}
+
+/// problems found by lints, they dont stop the compilation unless their lints are denied
+///
+/// functions may be generated in parallel, so warnings are collected behind a lock
+#[derive(Debug, Default)]
+pub struct Warnings {
+    lints: Lints,
+    warnings: std::sync::Mutex<Vec<(Lint, Span, Error)>>,
+}
+
+impl Warnings {
+    pub fn new(lints: Lints) -> Self {
+        Self {
+            lints,
+            warnings: Default::default(),
+        }
+    }
+
+    /// warnings with where they are, warnings of allowed lints are dropped
+    pub fn warn(&self, warnings: impl IntoIterator<Item = (Lint, Span, Error)>) {
+        let warnings = warnings
+            .into_iter()
+            .filter(|(lint, ..)| self.lints.level(*lint) != Level::Allow);
+        self.warnings.lock().unwrap().extend(warnings);
+    }
+
+    /// take all warnings, in the order they are in the source file
+    ///
+    /// warnings of denied lints are [`Severity::Error`], others are [`Severity::Warning`].
+    /// the first warning of each lint is noted with how its level is set
+    pub fn take(&mut self) -> Vec<Error> {
+        let mut warnings = std::mem::take(self.warnings.get_mut().unwrap());
+        warnings.sort_by_key(|(_, span, _)| span.start);
+
+        let mut noted = HashSet::new();
+        warnings
+            .into_iter()
+            .map(|(lint, _, warning)| {
+                let level = self.lints.level(lint);
+                let severity = match level {
+                    Level::Deny => Severity::Error,
+                    _ => Severity::Warning,
+                };
+                let warning = warning.with_severity(severity);
+                if !noted.insert(lint) {
+                    return warning;
+                }
+                match self.lints.is_default(lint) {
+                    true => warning + format!("note: `{} {lint}` is on by default", level.flag()),
+                    false => warning + format!("note: `{} {lint}` is set", level.flag()),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        for lint in Lint::ALL {
+            assert_eq!(Lint::from_name(lint.name()), Some(lint));
+        }
+        assert_eq!(Lint::from_name("unused_variables"), None);
+    }
+
+    #[test]
+    fn levels() {
+        let mut lints = Lints::default();
+        lints.set(Lint::UnusedVariables, Level::Allow);
+        lints.set(Lint::UnusedFunctions, Level::Deny);
+        let mut warnings = Warnings::new(lints);
+
+        let warn = |lint, start| {
+            let span = Span::new(start, start + 1);
+            (lint, span, span.make_error(lint))
+        };
+        warnings.warn([
+            warn(Lint::UnusedFunctions, 3),
+            warn(Lint::UnusedVariables, 1),
+            warn(Lint::UnreachableCode, 2),
+            warn(Lint::UnreachableCode, 0),
+        ]);
+
+        let severities = warnings
+            .take()
+            .iter()
+            .map(Error::severity)
+            .collect::<Vec<_>>();
+        let expect = [Severity::Warning, Severity::Warning, Severity::Error];
+        assert_eq!(severities, expect);
+        assert!(warnings.take().is_empty());
+    }
+
+    #[test]
+    fn found() {
+        use crate::parse::Items;
+        use crate::semantic::{Defines, Generate};
+
+        let src = "
+        zheng3 helper can1 zheng3 _a jie2 han2 fan3 1 fen1 jie2
+        dao3chu1 zheng3 f can1 zheng3 x fen1 zheng3 y jie2
+        han2
+            zheng3 stored fen1
+            stored wei2 2 fen1
+            ruo4 can1 x da4 0 jie2 han2
+                zheng3 x2 wei2 x fen1
+                ling4 stored wei2 x2 fen1
+                fan3 stored fen1
+            jie2
+            fan3 x fen1
+            fan3 0 fen1
+        jie2";
+        crate::parse_test(src, |p| {
+            let items = p.parse::<Items>()?;
+            let mut defines = Defines::<crate::semantic::mangle::DefaultMangler>::default();
+            assert!(defines.generate(&items[..]).is_ok());
+
+            let warnings = defines.warnings.take();
+            let reasons = [
+                "function `helper` is never used",
+                "unused parameter `y`",
+                "variable `stored` is assigned to, but never read",
+                "`stored` shadows a variable in an outer scope",
+                "unreachable statement",
+            ];
+            assert_eq!(warnings.len(), reasons.len());
+            for (warning, reason) in warnings.iter().zip(reasons) {
+                assert!(format!("{warning:?}").contains(reason), "{warning:?}");
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn unreachable() {
+        use crate::parse::Items;
+        use crate::semantic::{Defines, Generate};
+
+        let src = "
+        dao3chu1 zheng3 f can1 zheng3 x jie2
+        han2
+            zheng3 y wei2 x fen1
+            ruo4 can1 jia3 jie2 han2
+                ruo4 can1 jia3 jie2 han2
+                    fan3 1 fen1
+                jie2
+                fan3 2 fen1
+                y wei2 3 fen1
+            jie2
+            fan3 y fen1
+            y wei2 4 fen1
+        jie2";
+        crate::parse_test(src, |p| {
+            let items = p.parse::<Items>()?;
+            let mut defines = Defines::<crate::semantic::mangle::DefaultMangler>::default();
+            assert!(defines.generate(&items[..]).is_ok());
+
+            // blocks and statements in a block which is never executed are not warned again
+            let warnings = defines.warnings.take();
+            assert_eq!(warnings.len(), 2, "{warnings:?}");
+            assert!(matches!(
+                warnings[0].messages(),
+                [Message::Rich(reason, span), ..]
+                    if reason == "unreachable statement" && *span == Span::new(18, 27)
+            ));
+            assert!(matches!(
+                warnings[1].messages(),
+                [Message::Rich(reason, span), Message::Rich(note, at), ..]
+                    if reason == "unreachable statement"
+                        && *span == Span::new(38, 42)
+                        && note == "any code following this statement is unreachable"
+                        && *at == Span::new(35, 38)
+            ));
+            Ok(())
+        });
+    }
+}
@@ -1,12 +1,13 @@
+use py_ir::types::{PrimitiveType, TypeDecorators};
 use std::{borrow::Cow, fmt::Debug, marker::PhantomData};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ManglePrefix {
     Mod(String),
     Type(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MangleItem<'m> {
     Fn {
         name: Cow<'m, str>,
@@ -14,32 +15,148 @@ pub enum MangleItem<'m> {
         params: Vec<MangleUnit<'m>>,
     },
     Type {
+        /// outermost first, like [`py_ir::types::ComplexType::decorators`]
+        decorators: Cow<'m, [TypeDecorators]>,
         ty: Cow<'m, str>,
     },
-    Val(),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MangleUnit<'m> {
     pub prefix: Cow<'m, [ManglePrefix]>,
     pub item: MangleItem<'m>,
 }
 
+/// readable form of the unit, like `jia(i64, * u8)`
+///
+/// prefixes are separated by `::`, decorators are written like [`py_ir::types::ComplexType`]
+impl std::fmt::Display for MangleUnit<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut prefix = String::new();
+        for pf in self.prefix.iter() {
+            match pf {
+                ManglePrefix::Mod(s) | ManglePrefix::Type(s) => prefix += s,
+            }
+            prefix += "::";
+        }
+
+        match &self.item {
+            MangleItem::Fn { name, params } => {
+                write!(f, "{prefix}{name}(")?;
+                for (nth, param) in params.iter().enumerate() {
+                    if nth != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{param}")?;
+                }
+                f.write_str(")")
+            }
+            MangleItem::Type { decorators, ty } => {
+                let ty = py_ir::types::ComplexType {
+                    decorators: decorators.to_vec(),
+                    ty: prefix + ty,
+                };
+                write!(f, "{ty}")
+            }
+        }
+    }
+}
+
 pub trait Mangle: Sized + Send + Sync + 'static {
     fn mangle(unit: MangleUnit) -> String;
 
-    fn demangle(str: &str) -> MangleUnit<'static>;
+    /// the reverse of [`Mangle::mangle`]
+    ///
+    /// return [`None`] if the str is not mangled by this mangler
+    fn demangle(str: &str) -> Option<MangleUnit<'static>>;
 }
 
 pub type DefaultMangler = ChineseMangler;
 
+/// mangle items into readable names, like `jia 参 i64 指 u8 结`
+///
+/// - prefixes are followed by `的`(mods) or `之`(types)
+/// - parameters are listed between `参` and `结`
+/// - decorators are written before the type as keywords: `设`(const), `组`(array, followed by
+///   the length if it is sized), `引`(reference) and `指`(pointer)
+///
+/// names must not contain spaces, `的` or `之`, so hanzi names written in
+/// [`py_lex::LexMode::Unicode`] may not be demangled correctly
 pub struct ChineseMangler;
 
+impl ChineseMangler {
+    const FN_PARAMS: &'static str = "参";
+    const FN_END: &'static str = "结";
+    const MOD: char = '的';
+    const TYPE: char = '之';
+
+    fn decorator(decorator: &TypeDecorators) -> Cow<'static, str> {
+        match decorator {
+            TypeDecorators::Const => "设".into(),
+            TypeDecorators::Array => "组".into(),
+            TypeDecorators::Reference => "引".into(),
+            TypeDecorators::Pointer => "指".into(),
+            TypeDecorators::SizedArray(len) => format!("组 {len}").into(),
+        }
+    }
+
+    fn demangle_prefixed(str: &str) -> Option<(Vec<ManglePrefix>, String)> {
+        let mut prefix = vec![];
+        let mut name = String::new();
+        for c in str.chars() {
+            match c {
+                Self::MOD if !name.is_empty() => prefix.push(ManglePrefix::Mod(name.split_off(0))),
+                Self::TYPE if !name.is_empty() => {
+                    prefix.push(ManglePrefix::Type(name.split_off(0)))
+                }
+                Self::MOD | Self::TYPE => return None,
+                c => name.push(c),
+            }
+        }
+        (!name.is_empty()).then_some((prefix, name))
+    }
+
+    fn demangle_ty<'s, I>(words: &mut std::iter::Peekable<I>) -> Option<MangleUnit<'static>>
+    where
+        I: Iterator<Item = &'s str>,
+    {
+        let mut decorators = vec![];
+        let ty = loop {
+            let decorator = match words.next()? {
+                "设" => TypeDecorators::Const,
+                "引" => TypeDecorators::Reference,
+                "指" => TypeDecorators::Pointer,
+                "组" => match words.peek().and_then(|len| len.parse().ok()) {
+                    Some(len) => {
+                        words.next();
+                        TypeDecorators::SizedArray(len)
+                    }
+                    None => TypeDecorators::Array,
+                },
+                ty => break ty,
+            };
+            decorators.push(decorator);
+        };
+        if ty == Self::FN_PARAMS || ty == Self::FN_END {
+            return None;
+        }
+        let (prefix, ty) = Self::demangle_prefixed(ty)?;
+        Some(MangleUnit {
+            prefix: prefix.into(),
+            item: MangleItem::Type {
+                decorators: decorators.into(),
+                ty: ty.into(),
+            },
+        })
+    }
+}
+
 impl Mangle for ChineseMangler {
     fn mangle(unit: MangleUnit) -> String {
         fn mangle_prefex(prefix: &[ManglePrefix]) -> String {
             prefix.iter().fold(String::new(), |buffer, pf| match pf {
-                ManglePrefix::Mod(s) | ManglePrefix::Type(s) => buffer + &format!("{s}的"),
+                ManglePrefix::Mod(s) => buffer + s + &ChineseMangler::MOD.to_string(),
+                ManglePrefix::Type(s) => buffer + s + &ChineseMangler::TYPE.to_string(),
             })
         }
 
@@ -48,19 +165,319 @@ impl Mangle for ChineseMangler {
         match unit.item {
             MangleItem::Fn { name, params } => {
                 use std::fmt::Write;
-                let mut output = format!("{prefix}{name} 参");
+                let mut output = format!("{prefix}{name} {}", Self::FN_PARAMS);
                 for param in params.into_iter() {
                     write!(&mut output, " {}", Self::mangle(param)).ok();
                 }
-                format!("{output} 结")
+                format!("{output} {}", Self::FN_END)
+            }
+            MangleItem::Type { decorators, ty } => {
+                let mut output = String::new();
+                for decorator in decorators.iter() {
+                    output += &Self::decorator(decorator);
+                    output += " ";
+                }
+                output + &prefix + &ty
+            }
+        }
+    }
+
+    fn demangle(str: &str) -> Option<MangleUnit<'static>> {
+        let mut words = str.split(' ').peekable();
+
+        if !str.contains(Self::FN_PARAMS) {
+            let ty = Self::demangle_ty(&mut words)?;
+            return words.next().is_none().then_some(ty);
+        }
+
+        let (prefix, name) = Self::demangle_prefixed(words.next()?)?;
+        if words.next()? != Self::FN_PARAMS {
+            return None;
+        }
+        let mut params = vec![];
+        while words.peek()? != &Self::FN_END {
+            params.push(Self::demangle_ty(&mut words)?);
+        }
+        words.next();
+        if words.next().is_some() {
+            return None;
+        }
+
+        Some(MangleUnit {
+            prefix: prefix.into(),
+            item: MangleItem::Fn {
+                name: name.into(),
+                params,
+            },
+        })
+    }
+}
+
+/// mangle items into ascii, linker-safe names in the style of the Itanium C++ ABI, like
+/// `_Z3jiaxPhKA4_i5dian3`
+///
+/// - names are prefixed with their length, prefixed names are nested between `N` and `E`, and
+///   type prefixes are marked with `T`
+/// - primitive types are written as Itanium builtin types (`x` for `i64`, `h` for `u8`...),
+///   a function without parameters is written with `v`
+/// - decorators are written before the type: `K`(const), `A_`(array), `A<len>_`(sized
+///   array), `R`(reference) and `P`(pointer)
+/// - names which are not ascii are marked with `u`, `_` is escaped as `__` and other
+///   characters as their code point in hex between `_`, like `u12_4e00__4e01_` for `一丁`
+pub struct AsciiMangler;
+
+impl AsciiMangler {
+    const PREFIX: &'static str = "_Z";
+
+    fn builtin(ty: PrimitiveType) -> char {
+        match ty {
+            PrimitiveType::Bool => 'b',
+            PrimitiveType::I8 => 'a',
+            PrimitiveType::U8 => 'h',
+            PrimitiveType::I16 => 's',
+            PrimitiveType::U16 => 't',
+            PrimitiveType::I32 => 'i',
+            PrimitiveType::U32 => 'j',
+            PrimitiveType::I64 => 'x',
+            PrimitiveType::U64 => 'y',
+            PrimitiveType::I128 => 'n',
+            PrimitiveType::U128 => 'o',
+            PrimitiveType::Usize => 'm',
+            PrimitiveType::Isize => 'l',
+            PrimitiveType::F32 => 'f',
+            PrimitiveType::F64 => 'd',
+        }
+    }
+
+    fn from_builtin(code: char) -> Option<PrimitiveType> {
+        Some(match code {
+            'b' => PrimitiveType::Bool,
+            'a' => PrimitiveType::I8,
+            'h' => PrimitiveType::U8,
+            's' => PrimitiveType::I16,
+            't' => PrimitiveType::U16,
+            'i' => PrimitiveType::I32,
+            'j' => PrimitiveType::U32,
+            'x' => PrimitiveType::I64,
+            'y' => PrimitiveType::U64,
+            'n' => PrimitiveType::I128,
+            'o' => PrimitiveType::U128,
+            'm' => PrimitiveType::Usize,
+            'l' => PrimitiveType::Isize,
+            'f' => PrimitiveType::F32,
+            'd' => PrimitiveType::F64,
+            _ => return None,
+        })
+    }
+
+    fn source_name(output: &mut String, name: &str) {
+        use std::fmt::Write;
+        if name.is_ascii() {
+            write!(output, "{}{name}", name.len()).ok();
+            return;
+        }
+        let mut escaped = String::new();
+        for c in name.chars() {
+            match c {
+                '_' => escaped += "__",
+                c if c.is_ascii_alphanumeric() => escaped.push(c),
+                c => _ = write!(&mut escaped, "_{:x}_", c as u32),
+            }
+        }
+        write!(output, "u{}{escaped}", escaped.len()).ok();
+    }
+
+    /// write `name` with `prefix`, nested between `N` and `E` if there are any prefixes
+    fn nested_name(output: &mut String, prefix: &[ManglePrefix], name: &str) {
+        if prefix.is_empty() {
+            return Self::source_name(output, name);
+        }
+        output.push('N');
+        for pf in prefix {
+            match pf {
+                ManglePrefix::Mod(s) => Self::source_name(output, s),
+                ManglePrefix::Type(s) => {
+                    output.push('T');
+                    Self::source_name(output, s)
+                }
+            }
+        }
+        Self::source_name(output, name);
+        output.push('E');
+    }
+
+    fn mangle_ty(output: &mut String, unit: &MangleUnit) {
+        use std::fmt::Write;
+        let MangleItem::Type { decorators, ty } = &unit.item else {
+            unreachable!("parameters must be types")
+        };
+        for decorator in decorators.iter() {
+            match decorator {
+                TypeDecorators::Const => output.push('K'),
+                TypeDecorators::Array => output.push_str("A_"),
+                TypeDecorators::Reference => output.push('R'),
+                TypeDecorators::Pointer => output.push('P'),
+                TypeDecorators::SizedArray(len) => _ = write!(output, "A{len}_"),
+            }
+        }
+        match ty.parse() {
+            Ok(pty) if unit.prefix.is_empty() => output.push(Self::builtin(pty)),
+            _ => Self::nested_name(output, &unit.prefix, ty),
+        }
+    }
+
+    fn demangle_len(str: &mut &str) -> Option<usize> {
+        let len = str.find(|c: char| !c.is_ascii_digit()).unwrap_or(str.len());
+        // leading zeros are not allowed, so each name has only one mangled form
+        if len == 0 || (len > 1 && str.starts_with('0')) {
+            return None;
+        }
+        let (digits, rest) = str.split_at(len);
+        *str = rest;
+        digits.parse().ok()
+    }
+
+    fn demangle_source_name(str: &mut &str) -> Option<String> {
+        let escaped = str.starts_with('u');
+        if escaped {
+            *str = &str[1..];
+        }
+        let len = Self::demangle_len(str)?;
+        let name = str.get(..len)?;
+        *str = &str[len..];
+        if !escaped {
+            return (!name.is_empty()).then(|| name.to_owned());
+        }
+
+        let mut unescaped = String::new();
+        let mut chars = name.split('_');
+        unescaped += chars.next()?;
+        while let Some(code) = chars.next() {
+            match code {
+                "" => unescaped.push('_'),
+                code => unescaped.push(char::from_u32(u32::from_str_radix(code, 16).ok()?)?),
+            }
+            unescaped += chars.next()?;
+        }
+        // escaped ascii names would be mangled without `u`
+        (!unescaped.is_ascii()).then_some(unescaped)
+    }
+
+    fn demangle_nested_name(str: &mut &str) -> Option<(Vec<ManglePrefix>, String)> {
+        if !str.starts_with('N') {
+            return Some((vec![], Self::demangle_source_name(str)?));
+        }
+        *str = &str[1..];
+        let mut prefix = vec![];
+        loop {
+            let is_type = str.starts_with('T');
+            if is_type {
+                *str = &str[1..];
+            }
+            let name = Self::demangle_source_name(str)?;
+            if let Some(rest) = str.strip_prefix('E') {
+                *str = rest;
+                return (!is_type && !prefix.is_empty()).then_some((prefix, name));
+            }
+            prefix.push(match is_type {
+                true => ManglePrefix::Type(name),
+                false => ManglePrefix::Mod(name),
+            });
+        }
+    }
+
+    fn demangle_ty(str: &mut &str) -> Option<MangleUnit<'static>> {
+        let mut decorators = vec![];
+        loop {
+            let decorator = match str.chars().next()? {
+                'K' => TypeDecorators::Const,
+                'R' => TypeDecorators::Reference,
+                'P' => TypeDecorators::Pointer,
+                'A' => {
+                    *str = &str[1..];
+                    let decorator = match str.starts_with('_') {
+                        true => TypeDecorators::Array,
+                        false => TypeDecorators::SizedArray(Self::demangle_len(str)?),
+                    };
+                    *str = str.strip_prefix('_')?;
+                    decorators.push(decorator);
+                    continue;
+                }
+                _ => break,
+            };
+            *str = &str[1..];
+            decorators.push(decorator);
+        }
+
+        let builtin = str.chars().next().and_then(Self::from_builtin);
+        let (prefix, ty) = match builtin {
+            Some(pty) => {
+                *str = &str[1..];
+                (vec![], pty.to_string())
             }
-            MangleItem::Type { ty } => prefix + &ty,
-            MangleItem::Val() => todo!(),
+            None => Self::demangle_nested_name(str)?,
+        };
+        // primitive types are always mangled as builtin types
+        if prefix.is_empty() && builtin.is_none() && ty.parse::<PrimitiveType>().is_ok() {
+            return None;
         }
+        Some(MangleUnit {
+            prefix: prefix.into(),
+            item: MangleItem::Type {
+                decorators: decorators.into(),
+                ty: ty.into(),
+            },
+        })
     }
+}
 
-    fn demangle(_str: &str) -> MangleUnit<'static> {
-        todo!()
+impl Mangle for AsciiMangler {
+    fn mangle(unit: MangleUnit) -> String {
+        match &unit.item {
+            MangleItem::Fn { name, params } => {
+                let mut output = Self::PREFIX.to_owned();
+                Self::nested_name(&mut output, &unit.prefix, name);
+                if params.is_empty() {
+                    output.push('v');
+                }
+                for param in params {
+                    Self::mangle_ty(&mut output, param);
+                }
+                output
+            }
+            MangleItem::Type { .. } => {
+                let mut output = String::new();
+                Self::mangle_ty(&mut output, &unit);
+                output
+            }
+        }
+    }
+
+    fn demangle(str: &str) -> Option<MangleUnit<'static>> {
+        let Some(mut str) = str.strip_prefix(Self::PREFIX) else {
+            let mut str = str;
+            let ty = Self::demangle_ty(&mut str)?;
+            return str.is_empty().then_some(ty);
+        };
+
+        let (prefix, name) = Self::demangle_nested_name(&mut str)?;
+        let mut params = vec![];
+        if str != "v" {
+            while !str.is_empty() {
+                params.push(Self::demangle_ty(&mut str)?);
+            }
+            if params.is_empty() {
+                return None;
+            }
+        }
+
+        Some(MangleUnit {
+            prefix: prefix.into(),
+            item: MangleItem::Fn {
+                name: name.into(),
+                params,
+            },
+        })
     }
 }
 
@@ -87,7 +504,7 @@ impl<M: Mangle> Mangler<M> {
         }
     }
 
-    fn mangle_unit<'m>(&'m self, item: MangleItem<'m>) -> MangleUnit {
+    fn mangle_unit<'m>(&'m self, item: MangleItem<'m>) -> MangleUnit<'m> {
         MangleUnit {
             prefix: Cow::Borrowed(&self.prefix),
             item,
@@ -99,18 +516,21 @@ impl<M: Mangle> Mangler<M> {
         M::mangle(unit)
     }
 
-    pub fn mangle_ty(&self, ty: &py_ir::types::TypeDefine) -> MangleUnit {
+    pub fn mangle_ty<'m>(&'m self, ty: &'m py_ir::types::TypeDefine) -> MangleUnit<'m> {
         match ty {
             py_ir::types::TypeDefine::Primitive(pty) => self.mangle_unit(MangleItem::Type {
+                decorators: Cow::Borrowed(&[]),
                 ty: Cow::Owned(pty.to_string()),
             }),
-            py_ir::types::TypeDefine::Complex(_) => todo!(),
+            py_ir::types::TypeDefine::Complex(cty) => self.mangle_unit(MangleItem::Type {
+                decorators: Cow::Borrowed(&cty.decorators),
+                ty: Cow::Borrowed(&cty.ty),
+            }),
         }
     }
 
-    pub fn mangle_fn(&self, name: &str, sign: &py_declare::defs::FnSign) -> String {
-        let params = sign
-            .params
+    pub fn mangle_fn(&self, name: &str, params: &[py_declare::defs::Parameter]) -> String {
+        let params = params
             .iter()
             .map(|param| self.mangle_ty(&param.ty))
             .collect::<Vec<_>>();
@@ -120,3 +540,178 @@ impl<M: Mangle> Mangler<M> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use py_ir::types::{ComplexType, PrimitiveType, TypeDefine};
+
+    fn mangle_fn<M: Mangle>(name: &str, params: &[TypeDefine]) -> String {
+        let params = params
+            .iter()
+            .map(|ty| py_declare::defs::Parameter {
+                name: "x".to_owned(),
+                ty: ty.clone(),
+            })
+            .collect::<Vec<_>>();
+        Mangler::<M>::default().mangle_fn(name, &params)
+    }
+
+    fn complex_params() -> [TypeDefine; 4] {
+        let pointer = ComplexType {
+            decorators: vec![TypeDecorators::Pointer],
+            ty: "u8".to_owned(),
+        };
+        let array = ComplexType {
+            decorators: vec![TypeDecorators::Const, TypeDecorators::SizedArray(4)],
+            ty: "i32".to_owned(),
+        };
+        [
+            PrimitiveType::I64.into(),
+            pointer.into(),
+            array.into(),
+            ComplexType::no_decorators("dian3".to_owned()).into(),
+        ]
+    }
+
+    #[test]
+    fn chinese_complex_params() {
+        let mangled = mangle_fn::<ChineseMangler>("jia", &complex_params());
+        assert_eq!(mangled, "jia 参 i64 指 u8 设 组 4 i32 dian3 结");
+        assert!(ChineseMangler::demangle(&mangled).is_some());
+    }
+
+    #[test]
+    fn ascii_complex_params() {
+        let mangled = mangle_fn::<AsciiMangler>("jia", &complex_params());
+        assert_eq!(mangled, "_Z3jiaxPhKA4_i5dian3");
+        assert!(AsciiMangler::demangle(&mangled).is_some());
+        assert_eq!(mangle_fn::<AsciiMangler>("jia", &[]), "_Z3jiav");
+    }
+
+    #[test]
+    fn readable() {
+        let mangled = mangle_fn::<AsciiMangler>("jia", &complex_params());
+        let unit = AsciiMangler::demangle(&mangled).unwrap();
+        assert_eq!(unit.to_string(), "jia(i64, * u8, const [4] i32, dian3)");
+
+        let unit = ChineseMangler::demangle("shu4的dian3之jia 参 结").unwrap();
+        assert_eq!(unit.to_string(), "shu4::dian3::jia()");
+    }
+
+    #[test]
+    fn ascii_unicode_name() {
+        let mangled = mangle_fn::<AsciiMangler>("加_法", &[PrimitiveType::I64.into()]);
+        assert!(mangled.is_ascii());
+        assert_eq!(mangled, "_Zu14_52a0____6cd5_x");
+    }
+
+    #[test]
+    fn chinese_not_mangled() {
+        for str in [
+            "",
+            "jia 参",
+            "jia 参 指 结",
+            "jia 参 i64",
+            "的jia 参 结",
+            "i64 u8",
+        ] {
+            assert!(ChineseMangler::demangle(str).is_none(), "{str}");
+        }
+    }
+
+    #[test]
+    fn ascii_not_mangled() {
+        for str in [
+            "",
+            "_Z",
+            "_Z3jia",
+            "_Z3jiaP",
+            "_Z4jiax",
+            "_Z03jiav",
+            "_ZN3jiaEv",
+            "_ZN3fooT3jiaEv",
+            "_Z3jiavx",
+            "3i64",
+            "xx",
+        ] {
+            assert!(AsciiMangler::demangle(str).is_none(), "{str}");
+        }
+    }
+
+    fn name() -> impl Strategy<Value = String> {
+        // hanzi in the range are not keywords of the mangler
+        "[a-zA-Z_一-丿][a-zA-Z0-9_一-丿]{0,8}"
+    }
+
+    fn prefix() -> impl Strategy<Value = Vec<ManglePrefix>> {
+        let prefix = prop_oneof![
+            name().prop_map(ManglePrefix::Mod),
+            name().prop_map(ManglePrefix::Type),
+        ];
+        prop::collection::vec(prefix, 0..3)
+    }
+
+    fn decorator() -> impl Strategy<Value = TypeDecorators> {
+        prop_oneof![
+            Just(TypeDecorators::Const),
+            Just(TypeDecorators::Array),
+            Just(TypeDecorators::Reference),
+            Just(TypeDecorators::Pointer),
+            any::<usize>().prop_map(TypeDecorators::SizedArray),
+        ]
+    }
+
+    fn ty() -> impl Strategy<Value = MangleUnit<'static>> {
+        let decorators = prop::collection::vec(decorator(), 0..4);
+        (prefix(), decorators, name()).prop_map(|(prefix, decorators, ty)| MangleUnit {
+            prefix: prefix.into(),
+            item: MangleItem::Type {
+                decorators: decorators.into(),
+                ty: ty.into(),
+            },
+        })
+    }
+
+    fn function() -> impl Strategy<Value = MangleUnit<'static>> {
+        let params = prop::collection::vec(ty(), 0..5);
+        (prefix(), name(), params).prop_map(|(prefix, name, params)| MangleUnit {
+            prefix: prefix.into(),
+            item: MangleItem::Fn {
+                name: name.into(),
+                params,
+            },
+        })
+    }
+
+    fn round_trip<M: Mangle>(unit: MangleUnit<'static>) -> Result<(), TestCaseError> {
+        let mangled = M::mangle(unit.clone());
+        prop_assert_eq!(M::demangle(&mangled), Some(unit));
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn chinese_type_round_trip(unit in ty()) {
+            round_trip::<ChineseMangler>(unit)?;
+        }
+
+        #[test]
+        fn chinese_fn_round_trip(unit in function()) {
+            round_trip::<ChineseMangler>(unit)?;
+        }
+
+        #[test]
+        fn ascii_type_round_trip(unit in ty()) {
+            round_trip::<AsciiMangler>(unit)?;
+        }
+
+        #[test]
+        fn ascii_fn_round_trip(unit in function()) {
+            let mangled = AsciiMangler::mangle(unit.clone());
+            prop_assert!(mangled.is_ascii(), "{}", mangled);
+            round_trip::<AsciiMangler>(unit)?;
+        }
+    }
+}
@@ -1,5 +1,7 @@
+mod calls;
 mod generator;
 mod scope;
 pub use generator::*;
 pub use scope::*;
+pub mod lint;
 pub mod mangle;
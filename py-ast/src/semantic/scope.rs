@@ -1,5 +1,6 @@
 use super::mangle::*;
 use py_declare::*;
+use py_ir::types::TypeDefine;
 
 use std::collections::HashMap;
 use terl::*;
@@ -7,6 +8,7 @@ use terl::*;
 pub struct Defines<M: Mangle = DefaultMangler> {
     pub defs: Defs,
     pub mangler: Mangler<M>,
+    pub warnings: super::lint::Warnings,
 }
 
 impl<M: Mangle> Defines<M> {
@@ -18,7 +20,7 @@ impl<M: Mangle> Defines<M> {
         fn_define: &crate::parse::FnDefine,
         fn_sign: defs::FnSign,
     ) -> Result<String> {
-        let mangled_name = self.mangler.mangle_fn(&fn_define.name, &fn_sign);
+        let mangled_name = self.mangler.mangle_fn(&fn_define.name, &fn_sign.params);
 
         if let Some(previous) = self.defs.try_get_mangled(&mangled_name) {
             let previous_define = previous
@@ -45,13 +47,18 @@ impl<M: Mangle> Default for Defines<M> {
         Self {
             defs: Default::default(),
             mangler: Default::default(),
+            warnings: Default::default(),
         }
     }
 }
 
 impl<M: Mangle> Defines<M> {
     pub fn new(defs: Defs, mangler: Mangler<M>) -> Self {
-        Self { defs, mangler }
+        Self {
+            defs,
+            mangler,
+            warnings: Default::default(),
+        }
     }
 }
 
@@ -64,39 +71,63 @@ impl<M: Mangle> Defines<M> {
 ///
 ///
 /// then [`DeclareMap`] will declare them and output [`py_ir::Statements`],  or a [`Error`] will be thrown
-#[derive(Default)]
 pub struct FnScope {
     // mangled
     pub fn_name: String,
+    /// return type of the function, a placeholder group if it should be inferred
+    pub ret: GroupIdx,
     // a counter
     temps: usize,
-    parameters: HashMap<String, GroupIdx>,
+    parameters: HashMap<String, defs::VarDef>,
     pub declare_map: DeclareGraph,
 }
 
 impl FnScope {
-    pub fn new<'p, PI, SI>(fn_name: impl ToString, params: PI, spans: SI) -> Self
+    /// the return type is inferred from `fan3` statements if `ret` is [`None`]
+    pub fn new<'p, PI, SI>(
+        fn_name: impl ToString,
+        ret: Option<&TypeDefine>,
+        retty_span: Span,
+        params: PI,
+        spans: SI,
+    ) -> Self
     where
         PI: IntoIterator<Item = &'p defs::Parameter>,
         SI: IntoIterator<Item = Span>,
     {
         let mut declare_map = DeclareGraph::default();
+        let ret = match ret {
+            Some(ty) => {
+                declare_map.new_static_group(retty_span, std::iter::once(ty.clone().into()))
+            }
+            None => {
+                let placeholder = Placeholder {
+                    expect: "any type",
+                    accept: |_| true,
+                };
+                declare_map.new_placeholder_group(retty_span, placeholder)
+            }
+        };
         let parameters = spans
             .into_iter()
             .zip(params)
             .map(|(at, param)| {
-                (
-                    param.name.clone(),
-                    declare_map.new_static_group(at, std::iter::once(param.ty.clone().into())),
-                )
+                let ty = declare_map.new_static_group(at, std::iter::once(param.ty.clone().into()));
+                let def = defs::VarDef {
+                    ty,
+                    mutable: false,
+                    span: at,
+                };
+                (param.name.clone(), def)
             })
             .collect();
 
         Self {
             fn_name: fn_name.to_string(),
+            ret,
+            temps: 0,
             parameters,
             declare_map,
-            ..Default::default()
         }
     }
 
@@ -108,10 +139,19 @@ impl FnScope {
 
     #[inline]
     pub fn search_parameter(&mut self, name: &str) -> Option<defs::VarDef> {
-        self.parameters.get(name).map(|ty| defs::VarDef {
-            ty: *ty,
-            mutable: false,
-        })
+        self.parameters.get(name).cloned()
+    }
+
+    /// names of all parameters
+    pub fn parameter_names(&self) -> impl Iterator<Item = &str> {
+        self.parameters.keys().map(String::as_str)
+    }
+
+    /// all parameters with their names
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, &defs::VarDef)> {
+        self.parameters
+            .iter()
+            .map(|(name, def)| (name.as_str(), def))
     }
 }
 
@@ -159,6 +199,19 @@ impl BasicScopes {
         None
     }
 
+    /// names of all variables which are visible in current scope
+    pub fn variable_names(&self) -> impl Iterator<Item = &str> {
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.vars.keys().map(String::as_str))
+    }
+
+    /// search the variable in scopes outside the current one
+    pub fn search_outer_variable(&self, name: &str) -> Option<&defs::VarDef> {
+        let outers = &self.scopes[..self.scopes.len() - 1];
+        outers.iter().rev().find_map(|scope| scope.vars.get(name))
+    }
+
     fn current_scope(&mut self) -> &mut BasicScope {
         self.scopes.last_mut().unwrap()
     }
@@ -1,4 +1,5 @@
 mod translate;
+pub use translate::{decode_base32, encode_base32};
 
 pub struct CBackend;
 
@@ -24,7 +25,8 @@ struct Label(String);
 
 impl FileModule {
     pub fn new(name: String) -> Self {
-        const HEADER_FILES: &str = "#include <math.h>\n#include <stdbool.h>\n#include <stdint.h>\n";
+        const HEADER_FILES: &str =
+            "#include <math.h>\n#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n";
         Self {
             name,
             buffer: Buffer::C,
@@ -87,6 +89,22 @@ impl FileModule {
         self.goto(or)
     }
 
+    /// compute the arm, and store its value into `var`
+    fn select_arm(
+        &mut self,
+        var: &str,
+        arm: &py_ir::SelectArm<py_ir::value::Value>,
+    ) -> Result<(), std::fmt::Error> {
+        self.write_char('{')?;
+        for stmt in &*arm.compute {
+            self.translate(stmt)?;
+        }
+        write!(self, "{var}=")?;
+        self.translate(&arm.val)?;
+        self.eol()?;
+        self.write_char('}')
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         &self.name
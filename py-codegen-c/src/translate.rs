@@ -6,10 +6,46 @@ use std::fmt::Write;
 
 use py_ir::value::Value as IRValue;
 
-fn encode_base32(src: &str) -> String {
+/// names of functions are written as `_` followed by the mangled name encoded with this
+pub fn encode_base32(src: &str) -> String {
     base32::encode(base32::Alphabet::Crockford, src.as_bytes())
 }
 
+/// the reverse of [`encode_base32`]
+///
+/// return [`None`] if the str is not encoded from an utf-8 str
+pub fn decode_base32(src: &str) -> Option<String> {
+    let bytes = base32::decode(base32::Alphabet::Crockford, src)?;
+    String::from_utf8(bytes).ok()
+}
+
+/// binary operators which are written between operands in c
+fn binary_operator(op: py_lex::ops::Operators) -> Option<&'static str> {
+    let op = match op {
+        py_lex::ops::Operators::Add => "+",
+        py_lex::ops::Operators::Sub => "-",
+        py_lex::ops::Operators::Mul => "*",
+        py_lex::ops::Operators::Div => "/",
+        py_lex::ops::Operators::Mod => "%",
+        py_lex::ops::Operators::Eq => "==",
+        py_lex::ops::Operators::Neq => "!=",
+        py_lex::ops::Operators::Gt => ">",
+        py_lex::ops::Operators::Lt => "<",
+        py_lex::ops::Operators::Ge => ">=",
+        py_lex::ops::Operators::Le => "<=",
+        py_lex::ops::Operators::And => "&&",
+        py_lex::ops::Operators::Or => "||",
+        py_lex::ops::Operators::Band => "&",
+        py_lex::ops::Operators::Bor => "|",
+        py_lex::ops::Operators::Xor => "^",
+        py_lex::ops::Operators::Shl => "<<",
+        py_lex::ops::Operators::Shr => ">>",
+        py_lex::ops::Operators::GetElement => ".",
+        _ => return None,
+    };
+    Some(op)
+}
+
 impl Translate<py_ir::Item<IRValue>> for crate::FileModule {
     fn translate(&mut self, item: &py_ir::Item) -> std::fmt::Result {
         match item {
@@ -68,6 +104,22 @@ impl Translate<py_ir::Statement<IRValue>> for crate::FileModule {
 }
 impl Translate<py_ir::VarDefine<IRValue>> for crate::FileModule {
     fn translate(&mut self, item: &py_ir::VarDefine<IRValue>) -> std::fmt::Result {
+        if let Some(py_ir::value::AssignValue::Select(select)) = &item.init {
+            self.translate(&select.cond)?;
+            // arms which need computing cant be written in `?:`, so they are stored into the
+            // variable in `if`
+            if !select.then.compute.is_empty() || !select.else_.compute.is_empty() {
+                self.translate(&item.ty)?;
+                write!(self, " {}", item.name)?;
+                self.eol()?;
+                self.write_str("if(")?;
+                self.translate(&select.cond.val)?;
+                self.write_char(')')?;
+                self.select_arm(&item.name, &select.then)?;
+                self.write_str("else")?;
+                return self.select_arm(&item.name, &select.else_);
+            }
+        }
         self.translate(&item.ty)?;
         write!(self, " {}", item.name)?;
         if let Some(init) = &item.init {
@@ -80,7 +132,21 @@ impl Translate<py_ir::VarDefine<IRValue>> for crate::FileModule {
 impl Translate<py_ir::VarStore<IRValue>> for crate::FileModule {
     fn translate(&mut self, item: &py_ir::VarStore<IRValue>) -> std::fmt::Result {
         self.write_str(&item.name)?;
-        self.write_char('=')?;
+        match &item.compound {
+            Some(compound) => match binary_operator(compound.op) {
+                Some(op) => write!(self, "{op}=")?,
+                // operators like `pow` have no compound form in c
+                None => {
+                    self.write_char('=')?;
+                    let l = IRValue::Variable(item.name.clone());
+                    let operate = py_ir::value::Operate::Binary(compound.op, l, item.val.clone());
+                    let ty = *compound.ty.as_primitive().unwrap();
+                    self.translate(&py_ir::value::AssignValue::Operate(operate, ty))?;
+                    return self.eol();
+                }
+            },
+            None => self.write_char('=')?,
+        }
         self.translate(&item.val)?;
         self.eol()
     }
@@ -128,8 +194,10 @@ impl Translate<py_ir::While<IRValue>> for crate::FileModule {
         let lafter = self.label();
 
         self.translate(&lcond)?;
-        self.translate(&item.cond)?;
-        self.if_else(&item.cond.val, &lbody, &lafter)?;
+        if let Some(cond) = &item.cond {
+            self.translate(cond)?;
+            self.if_else(&cond.val, &lbody, &lafter)?;
+        }
 
         self.translate(&lbody)?;
         self.translate(&item.body)?;
@@ -171,9 +239,24 @@ impl Translate<py_ir::value::AssignValue> for crate::FileModule {
                 self.write_char(')')
             }
             py_ir::value::AssignValue::Value(value) => self.translate(value),
+            py_ir::value::AssignValue::Convert(convert) => {
+                self.write_char('(')?;
+                self.translate(&convert.to)?;
+                self.write_char(')')?;
+                self.translate(&convert.val)
+            }
+            // computing of the condition is translated before, see [`py_ir::VarDefine`]
+            py_ir::value::AssignValue::Select(select) => {
+                self.translate(&select.cond.val)?;
+                self.write_char('?')?;
+                self.translate(&select.then.val)?;
+                self.write_char(':')?;
+                self.translate(&select.else_.val)
+            }
             py_ir::value::AssignValue::Operate(op, _) => match op {
                 py_ir::value::Operate::Unary(op, v) => {
                     let op = match op {
+                        py_lex::ops::Operators::Neg => "-",
                         py_lex::ops::Operators::Not => "!",
                         py_lex::ops::Operators::Bnot => "~",
                         py_lex::ops::Operators::AddrOf => "&",
@@ -193,36 +276,15 @@ impl Translate<py_ir::value::AssignValue> for crate::FileModule {
                     self.write_str(op)?;
                     self.translate(v)
                 }
+                py_ir::value::Operate::Binary(py_lex::ops::Operators::Pow, l, r) => {
+                    self.write_str("pow(")?;
+                    self.translate(l)?;
+                    self.write_char(',')?;
+                    self.translate(r)?;
+                    self.write_char(')')
+                }
                 py_ir::value::Operate::Binary(op, l, r) => {
-                    let op = match op {
-                        py_lex::ops::Operators::Add => "+",
-                        py_lex::ops::Operators::Sub => "-",
-                        py_lex::ops::Operators::Mul => "*",
-                        py_lex::ops::Operators::Div => "/",
-                        py_lex::ops::Operators::Mod => "%",
-                        py_lex::ops::Operators::Eq => "==",
-                        py_lex::ops::Operators::Neq => "!=",
-                        py_lex::ops::Operators::Gt => ">",
-                        py_lex::ops::Operators::Lt => "<",
-                        py_lex::ops::Operators::Ge => ">=",
-                        py_lex::ops::Operators::Le => "<=",
-                        py_lex::ops::Operators::And => "&&",
-                        py_lex::ops::Operators::Or => "||",
-                        py_lex::ops::Operators::Band => "&",
-                        py_lex::ops::Operators::Bor => "|",
-                        py_lex::ops::Operators::Xor => "^",
-                        py_lex::ops::Operators::Shl => "<<",
-                        py_lex::ops::Operators::Shr => ">>",
-                        py_lex::ops::Operators::GetElement => ".",
-                        py_lex::ops::Operators::Pow => {
-                            self.write_str("pow(")?;
-                            self.translate(l)?;
-                            self.write_char(',')?;
-                            self.translate(r)?;
-                            return self.write_char(')');
-                        }
-                        _ => panic!("unreadable or todo"),
-                    };
+                    let op = binary_operator(*op).expect("unreadable or todo");
                     self.translate(l)?;
                     self.write_str(op)?;
                     self.translate(r)
@@ -235,6 +297,25 @@ impl Translate<py_ir::value::Value> for crate::FileModule {
     fn translate(&mut self, item: &py_ir::value::Value) -> std::fmt::Result {
         match item {
             IRValue::Variable(var) => self.write_str(var),
+            IRValue::Literal(py_ir::value::Literal::Null, _) => self.write_str("NULL"),
+            IRValue::Literal(py_ir::value::Literal::Char(c), _) => write!(self, "{}", *c as u32),
+            IRValue::Literal(py_ir::value::Literal::String(str), _) => {
+                // non-printable bytes(including utf-8 ones) are written in octal escapes
+                self.write_str("(uint8_t *)\"")?;
+                for byte in str.bytes() {
+                    match byte {
+                        b'"' | b'\\' | b'?' => write!(self, "\\{}", byte as char)?,
+                        b' '..=b'~' => write!(self, "{}", byte as char)?,
+                        _ => write!(self, "\\{byte:03o}")?,
+                    }
+                }
+                self.write_str("\"")
+            }
+            // C has no integer literals wider than 64 bits, so they are built from two halves
+            IRValue::Literal(py_ir::value::Literal::Integer(nu), _) if *nu > u64::MAX as u128 => {
+                let (hi, lo) = (*nu >> 64, *nu as u64);
+                write!(self, "((unsigned __int128){hi}ull << 64 | {lo}ull)")
+            }
             IRValue::Literal(l, _) => write!(self, "{l}"),
         }
     }
@@ -270,8 +351,25 @@ impl Translate<py_ir::types::PrimitiveType> for crate::FileModule {
     }
 }
 impl Translate<py_ir::types::ComplexType> for crate::FileModule {
-    fn translate(&mut self, _item: &py_ir::types::ComplexType) -> std::fmt::Result {
-        todo!()
+    fn translate(&mut self, item: &py_ir::types::ComplexType) -> std::fmt::Result {
+        use py_ir::types::{PrimitiveType, TypeDecorators};
+        match item.ty.parse::<PrimitiveType>() {
+            Ok(primitive) => self.translate(&primitive)?,
+            Err(_) => self.write_str(&item.ty)?,
+        }
+        for decorator in &item.decorators {
+            match decorator {
+                // unclear semantics
+                TypeDecorators::Const => {}
+                // sized arrays are passed as pointers to their first elements, like arrays in
+                // parameters of c functions
+                TypeDecorators::Array
+                | TypeDecorators::SizedArray(_)
+                | TypeDecorators::Reference
+                | TypeDecorators::Pointer => self.write_char('*')?,
+            }
+        }
+        Ok(())
     }
 }
 impl<Item> Translate<[Item]> for crate::FileModule
@@ -290,3 +388,39 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use py_ir::types::{ComplexType, TypeDecorators};
+
+    #[test]
+    fn complex_types() {
+        let mut module = crate::FileModule::new("test".to_owned());
+        let ty = ComplexType {
+            decorators: vec![TypeDecorators::SizedArray(4), TypeDecorators::Pointer],
+            ty: "i64".to_owned(),
+        };
+        let start = module.c_file().len();
+        module.translate(&ty).unwrap();
+        assert_eq!(&module.c_file()[start..], "int64_t**");
+    }
+
+    #[test]
+    fn wide_integers() {
+        let mut module = crate::FileModule::new("test".to_owned());
+        let start = module.c_file().len();
+        for nu in [u64::MAX as u128, u64::MAX as u128 + 1, u128::MAX] {
+            let literal = py_ir::value::Literal::Integer(nu);
+            let ty = py_ir::types::PrimitiveType::U128.into();
+            module.translate(&IRValue::Literal(literal, ty)).unwrap();
+            module.write_char(' ').unwrap();
+        }
+        assert_eq!(
+            &module.c_file()[start..],
+            "18446744073709551615 \
+            ((unsigned __int128)1ull << 64 | 0ull) \
+            ((unsigned __int128)18446744073709551615ull << 64 | 18446744073709551615ull) "
+        );
+    }
+}
@@ -37,6 +37,11 @@ fn type_scast<'ctx>(context: &'ctx Context, ty: &ir_types::TypeDefine) -> BasicT
             PrimitiveType::F32 => context.f32_type().into(),
             PrimitiveType::F64 => context.f64_type().into(),
         },
+        // opaque pointers: what a pointer points to doesnt matter
+        TypeDefine::Complex(ty) if !ty.decorators.is_empty() => context
+            .i8_type()
+            .ptr_type(inkwell::AddressSpace::default())
+            .into(),
         TypeDefine::Complex(_ty) => {
             todo!()
         }
@@ -88,6 +93,11 @@ impl CodeGen<py_ir::FnDefine<IRValue>> for ModuleGen<'_> {
         };
 
         fn_gen.generate(&cgu.body)?;
+        // the end of the function is never reached, but a block after a branch of `ruo4`
+        // which is always executed still needs a terminator
+        if !fn_gen.terminated() {
+            self.builder.build_unreachable()?;
+        }
 
         Ok(())
     }
@@ -106,6 +116,13 @@ impl<'mg, 'ctx> FnGen<'mg, 'ctx> {
         type_scast(self.context, ty)
     }
 
+    /// if the current block has been ended, like by `fan3`
+    fn terminated(&self) -> bool {
+        self.builder
+            .get_insert_block()
+            .is_some_and(|block| block.get_terminator().is_some())
+    }
+
     fn get_val(&self, name: &str) -> &(dyn Variable<'ctx> + 'ctx) {
         self.fn_scope
             .params
@@ -149,9 +166,25 @@ impl<'ctx> FnGen<'_, 'ctx> {
     fn literal(
         &self,
         literal: &ir_value::Literal,
-        ty: &ir_types::PrimitiveType,
+        ty: &ir_types::TypeDefine,
     ) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        if let ir_value::Literal::Null = literal {
+            assert!(ty.is_pointer(), "incorrect TypeDefine are passed in");
+            return Ok(self.type_cast(ty).into_pointer_type().const_null().into());
+        }
+        if let ir_value::Literal::String(str) = literal {
+            // rust strings are utf-8 already
+            let global = self.builder.build_global_string_ptr(str, "")?;
+            return Ok(global.as_pointer_value().into());
+        }
+
+        let ty = ty
+            .as_primitive()
+            .expect("incorrect TypeDefine are passed in");
         let ret = match literal {
+            ir_value::Literal::Bool(bool) if ty == &ir_types::PrimitiveType::Bool => {
+                self.context.bool_type().const_int(*bool as _, false).into()
+            }
             ir_value::Literal::Integer(int) if ty.is_integer() => match ty.width() {
                 1 => self
                     .context
@@ -181,7 +214,7 @@ impl<'ctx> FnGen<'_, 'ctx> {
                 128 => self
                     .context
                     .i128_type()
-                    .const_int(*int as _, ty.is_signed())
+                    .const_int_arbitrary_precision(&[*int as u64, (*int >> 64) as u64])
                     .into(),
                 _ => unreachable!(),
             },
@@ -207,7 +240,10 @@ impl<'ctx> FnGen<'_, 'ctx> {
         }
     }
 
-    fn eval_assign_val(&self, val: &IRAssignValue) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+    fn eval_assign_val(
+        &mut self,
+        val: &IRAssignValue,
+    ) -> Result<BasicValueEnum<'ctx>, BuilderError> {
         match val {
             IRAssignValue::Value(val) => self.eval_val(val),
             IRAssignValue::FnCall(fn_call) => {
@@ -236,7 +272,49 @@ impl<'ctx> FnGen<'_, 'ctx> {
                     crate::operators::binary(self.builder, *ty, *op, l, r, "")
                 }
             },
+            IRAssignValue::Select(select) => self.eval_select(select),
+            IRAssignValue::Convert(convert) => {
+                let val = self.eval_val(&convert.val)?;
+                let to_ty = self.type_cast(&convert.to.into());
+                crate::operators::convert(self.builder, convert.from, convert.to, to_ty, val, "")
+            }
+        }
+    }
+
+    /// br to arms, and choose the value with phi
+    fn eval_select(
+        &mut self,
+        select: &py_ir::Select<IRValue>,
+    ) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        let then = self.context.append_basic_block(self.current_fn, "");
+        let else_ = self.context.append_basic_block(self.current_fn, "");
+        let after = self.context.append_basic_block(self.current_fn, "");
+
+        self.generate(&select.cond)?;
+        let cond_val = self.eval_val(&select.cond.val)?.into_int_value();
+        self.builder
+            .build_conditional_branch(cond_val, then, else_)?;
+
+        let mut incomings = vec![];
+        for (block, arm) in [(then, &select.then), (else_, &select.else_)] {
+            self.builder.position_at_end(block);
+            self.fn_scope.vars.push(Default::default());
+            for stmt in &*arm.compute {
+                self.generate(stmt)?;
+            }
+            let val = self.eval_val(&arm.val)?;
+            self.fn_scope.vars.pop();
+            // computing may create new blocks, so the block jumps to `after` is the current one
+            incomings.push((val, self.builder.get_insert_block().unwrap()));
+            self.builder.build_unconditional_branch(after)?;
+        }
+
+        self.builder.position_at_end(after);
+        let phi = self.builder.build_phi(incomings[0].0.get_type(), "")?;
+        for (val, block) in &incomings {
+            phi.add_incoming(&[(val as &dyn BasicValue, *block)]);
         }
+        Ok(phi.as_basic_value())
     }
 }
 
@@ -307,6 +385,15 @@ impl CodeGen<py_ir::VarStore<IRValue>> for FnGen<'_, '_> {
     fn generate(&mut self, cgu: &py_ir::VarStore<IRValue>) -> Result<(), BuilderError> {
         let val = self.eval_val(&cgu.val)?;
         let s = self.get_val(&cgu.name);
+        let val = match &cgu.compound {
+            // read, modify and write the same variable
+            Some(compound) => {
+                let ty = *compound.ty.as_primitive().unwrap();
+                let l = s.load(self.builder)?;
+                crate::operators::binary(self.builder, ty, compound.op, l, val, "")?
+            }
+            None => val,
+        };
         s.store(self.builder, val)?;
         Ok(())
     }
@@ -381,20 +468,35 @@ impl CodeGen<py_ir::While<IRValue>> for FnGen<'_, '_> {
     fn generate(&mut self, cgu: &py_ir::While<IRValue>) -> Result<(), BuilderError> {
         let cond = self.context.append_basic_block(self.current_fn, "");
         let code = self.context.append_basic_block(self.current_fn, "");
-        let after = self.context.append_basic_block(self.current_fn, "");
 
+        self.builder.build_unconditional_branch(cond)?;
         self.builder.position_at_end(cond);
 
-        self.generate(&cgu.cond)?;
-        let cond_val = self.eval_val(&cgu.cond.val)?.into_int_value();
-        self.builder
-            .build_conditional_branch(cond_val, code, after)?;
+        // an infinite loop has no block after it, like `fan3`
+        let after = match &cgu.cond {
+            Some(condition) => {
+                let after = self.context.append_basic_block(self.current_fn, "");
+                self.generate(condition)?;
+                let cond_val = self.eval_val(&condition.val)?.into_int_value();
+                self.builder
+                    .build_conditional_branch(cond_val, code, after)?;
+                Some(after)
+            }
+            None => {
+                self.builder.build_unconditional_branch(code)?;
+                None
+            }
+        };
 
         self.builder.position_at_end(code);
         self.generate(&cgu.body)?;
-        self.builder.build_unconditional_branch(cond)?;
+        if !self.terminated() {
+            self.builder.build_unconditional_branch(cond)?;
+        }
 
-        self.builder.position_at_end(after);
+        if let Some(after) = after {
+            self.builder.position_at_end(after);
+        }
 
         Ok(())
     }
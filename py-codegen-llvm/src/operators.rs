@@ -1,5 +1,6 @@
 use inkwell::{
     builder::{Builder, BuilderError},
+    types::BasicTypeEnum,
     values::BasicValueEnum,
 };
 use py_ir::types::PrimitiveType;
@@ -131,14 +132,20 @@ pub fn unary<'ctx>(
     if ty.is_integer() {
         let val = val.into_int_value();
         let val = match op {
-            // Operators::Neg => builder.build_int_neg(val, name)?,
+            Operators::Neg => builder.build_int_neg(val, name)?,
             Operators::Not => builder.build_not(val, name)?,
             _ => unreachable!(),
         }
         .into();
         Ok(val)
     } else if ty.is_float() {
-        unreachable!()
+        let val = val.into_float_value();
+        let val = match op {
+            Operators::Neg => builder.build_float_neg(val, name)?,
+            _ => unreachable!(),
+        }
+        .into();
+        Ok(val)
     } else
     /* ty.is_bool()) */
     {
@@ -151,3 +158,36 @@ pub fn unary<'ctx>(
         Ok(val)
     }
 }
+
+/// lossless conversions only, see [`PrimitiveType::widens_to`]
+pub fn convert<'ctx>(
+    builder: &Builder<'ctx>,
+    from: PrimitiveType,
+    to: PrimitiveType,
+    to_ty: BasicTypeEnum<'ctx>,
+    val: BasicValueEnum<'ctx>,
+    name: &str,
+) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+    let val = if from.is_float() {
+        builder
+            .build_float_ext(val.into_float_value(), to_ty.into_float_type(), name)?
+            .into()
+    } else if to.is_float() {
+        let val = val.into_int_value();
+        if from.is_signed() {
+            builder.build_signed_int_to_float(val, to_ty.into_float_type(), name)?
+        } else {
+            builder.build_unsigned_int_to_float(val, to_ty.into_float_type(), name)?
+        }
+        .into()
+    } else {
+        let val = val.into_int_value();
+        if from.is_signed() {
+            builder.build_int_s_extend(val, to_ty.into_int_type(), name)?
+        } else {
+            builder.build_int_z_extend(val, to_ty.into_int_type(), name)?
+        }
+        .into()
+    };
+    Ok(val)
+}
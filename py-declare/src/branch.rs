@@ -21,16 +21,27 @@ impl Branch {
 #[derive(Default, Debug, Clone)]
 pub(crate) struct BranchDepend {
     pub(crate) depends: HashMap<GroupIdx, HashSet<usize>>,
+    /// implicit conversions needed if the branch depends on the branch of the group
+    pub(crate) costs: HashMap<GroupIdx, HashMap<usize, usize>>,
     pub(crate) failds: GroupError,
 }
 
 type GroupError = HashMap<GroupIdx, HashMap<usize, DeclareError>>;
 
 impl BranchDepend {
-    pub fn new_depends<E>(&mut self, group: GroupIdx, branches: HashSet<usize>, filtered: E)
+    /// `branches` are the branches which satisfy the filter, and how many implicit conversions
+    /// they need
+    pub fn new_depends<E>(&mut self, group: GroupIdx, branches: HashMap<usize, usize>, filtered: E)
     where
         E: Fn(Branch) -> DeclareError,
     {
+        // depending on a group more than once needs all the conversions
+        let costs = self.costs.entry(group).or_default();
+        for (&branch, &cost) in &branches {
+            *costs.entry(branch).or_default() += cost;
+        }
+        let branches = branches.into_keys().collect::<HashSet<_>>();
+
         let new_depend = match self.depends.remove(&group) {
             Some(previous) => {
                 let new_depend = previous
@@ -57,7 +68,13 @@ impl BranchDepend {
         self.depends.insert(group, new_depend);
     }
 
-    pub fn merge_depends<U>(mut self, mut use_branch: U) -> Result<Vec<HashSet<Branch>>, GroupError>
+    /// # Return
+    ///
+    /// all combinations of depended branches, with implicit conversions they need in total
+    pub fn merge_depends<U>(
+        mut self,
+        mut use_branch: U,
+    ) -> Result<Vec<(HashSet<Branch>, usize)>, GroupError>
     where
         U: FnMut(Branch) -> Branch,
     {
@@ -75,20 +92,24 @@ impl BranchDepend {
             }
         }
 
-        let mut groups: Vec<HashSet<Branch>> = vec![HashSet::default()];
+        let mut groups: Vec<(HashSet<Branch>, usize)> = vec![Default::default()];
         for (group, depend) in self.depends {
+            let costs = self.costs.remove(&group).unwrap_or_default();
             let len = groups.len();
             // resize depends
             //
             // depend.len() >= 1 because depend.len() == 0 is an error and solved above
             for _ in 1..depend.len() {
-                groups.extend(groups.clone());
+                groups.extend_from_within(..len);
             }
             // insert
             for (new_depend_idx, new_depend) in depend.into_iter().enumerate() {
                 let branch = use_branch(Branch::new(group, new_depend));
+                let cost = costs.get(&new_depend).copied().unwrap_or_default();
                 for depends_idx in 0..len {
-                    groups[new_depend_idx * len + depends_idx].insert(branch);
+                    let (depends, total) = &mut groups[new_depend_idx * len + depends_idx];
+                    depends.insert(branch);
+                    *total += cost;
                 }
             }
         }
@@ -116,6 +137,14 @@ impl BranchesBuilder {
         }
     }
 
+    /// a branch which is impossible from the beginning
+    pub fn failed(reason: DeclareError) -> Self {
+        Self {
+            state: Err(reason),
+            depends: Default::default(),
+        }
+    }
+
     /// # Return
     ///
     /// is self.state ok
@@ -155,8 +184,8 @@ impl BranchesBuilder {
         let satisfy_branches = map[depend].alives(|alives| {
             alives
                 .filter(|(.., ty)| filter.satisfy(ty))
-                .map(|(branch, ..)| branch.branch_idx)
-                .collect::<HashSet<_>>()
+                .map(|(branch, ty)| (branch.branch_idx, filter.conversions(ty)))
+                .collect::<HashMap<_, _>>()
         });
 
         let filtered_reason = DeclareError::Unexpect {
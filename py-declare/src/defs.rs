@@ -29,6 +29,11 @@ impl Defs {
     pub fn get_unmangled(&self, name: &str) -> Option<&[Overload]> {
         self.fn_signs.get_unmangled(name)
     }
+
+    /// names of all defined functions, before mangling
+    pub fn unmangled_names(&self) -> impl Iterator<Item = &str> {
+        self.fn_signs.unmangled_names()
+    }
 }
 
 #[derive(Default)]
@@ -47,6 +52,7 @@ impl FnSigns {
         let value = defs::FnSignWithName {
             sign,
             name: mangled.to_owned(),
+            unmangled: unmangled.to_owned(),
         };
 
         let overload: Overload = value.into();
@@ -64,6 +70,10 @@ impl FnSigns {
         self.unmangled.get(name).map(|v| &**v)
     }
 
+    pub fn unmangled_names(&self) -> impl Iterator<Item = &str> {
+        self.unmangled.keys().map(String::as_str)
+    }
+
     pub fn get_mangled(&self, name: &str) -> &Overload {
         self.mangled.get(name).unwrap()
     }
@@ -78,7 +88,9 @@ impl FnSigns {
 #[derive(Debug, Clone)]
 pub struct FnSignWithName {
     pub sign: FnSign,
+    /// mangled name
     pub name: String,
+    pub unmangled: String,
 }
 
 impl std::ops::Deref for FnSignWithName {
@@ -97,7 +109,7 @@ impl std::ops::DerefMut for FnSignWithName {
 
 #[derive(Debug, Clone)]
 pub struct FnSign {
-    /// return type of the function must be cleared (will change in future versions)
+    /// return type of the function, it has been inferred if the function is defined with `ling4`
     pub ty: TypeDefine,
     pub params: Vec<Parameter>,
     pub retty_span: Span,
@@ -117,9 +129,7 @@ impl FnSign {
 
 impl std::fmt::Display for FnSignWithName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // NOTO: this may only work with default Chinese Mangler
-        let unmangled = self.name.split_ascii_whitespace().next().unwrap();
-        f.write_str(unmangled)?;
+        f.write_str(&self.unmangled)?;
         f.write_str("(")?;
         match self.params.len() {
             0 => f.write_str(")")?,
@@ -127,7 +137,7 @@ impl std::fmt::Display for FnSignWithName {
             _ => {
                 f.write_fmt(format_args!("{}", self.params[0].ty))?;
                 for param in &self.params[1..] {
-                    f.write_fmt(format_args!(", {}", param.name))?;
+                    f.write_fmt(format_args!(", {}", param.ty))?;
                 }
                 f.write_str(")")?
             }
@@ -142,4 +152,6 @@ pub type Parameter = py_ir::Parameter<TypeDefine>;
 pub struct VarDef {
     pub ty: GroupIdx,
     pub mutable: bool,
+    /// where the variable is defined, which is unique for each variable
+    pub span: Span,
 }
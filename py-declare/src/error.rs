@@ -1,4 +1,5 @@
 use crate::Type;
+use py_ir::types::TypeDefine;
 use std::rc::Rc;
 use terl::{Message, Span, WithSpan};
 
@@ -29,6 +30,20 @@ pub enum DeclareError {
         left: Span,
         right: Span,
     },
+    /// another branch needs fewer implicit conversions
+    MoreConversions {
+        this: usize,
+        cheapest: usize,
+    },
+    /// nothing tells which type the literal should be, so the default one is picked
+    Defaulted {
+        default: TypeDefine,
+    },
+    /// a literal is too large(or too small) for the type
+    OutOfRange {
+        value: String,
+        ty: TypeDefine,
+    },
     WithLocation {
         location: Span,
         error: Box<DeclareError>,
@@ -64,6 +79,18 @@ impl DeclareError {
         }
     }
 
+    /// return the type if the error is [`DeclareError::OutOfRange`]
+    pub fn out_of_range(&self) -> Option<&TypeDefine> {
+        match self {
+            DeclareError::OutOfRange { ty, .. } => Some(ty),
+            DeclareError::WithLocation { error, .. } | DeclareError::WithPrevious { error, .. } => {
+                error.out_of_range()
+            }
+            DeclareError::Shared { err } => err.out_of_range(),
+            _ => None,
+        }
+    }
+
     fn generate_inner(&self, msgs: &mut Vec<terl::Message>) {
         match self {
             DeclareError::UniqueDeleted { reason } => {
@@ -94,6 +121,15 @@ impl DeclareError {
                 msgs.push((*left).into());
                 msgs.push((*right).into());
             }
+            DeclareError::OutOfRange { value, ty } => {
+                msgs.push(Message::Text(format!("{value} does not fit in {ty}")))
+            }
+            DeclareError::MoreConversions { this, cheapest } => msgs.push(Message::Text(format!(
+                "this needs {this} implicit conversions, but another one needs only {cheapest}"
+            ))),
+            DeclareError::Defaulted { default } => msgs.push(Message::Text(format!(
+                "nothing tells which type this should be, so it defaults to {default}"
+            ))),
             DeclareError::Declared { declare_as } => msgs.push(Message::Text(format!(
                 "this has been declared as {declare_as}"
             ))),
@@ -8,6 +8,15 @@ pub trait BranchFilter<T: Types>: WithSpan {
     fn satisfy(&self, ty: &Type) -> bool;
 
     fn expect(&self, defs: &Defs) -> String;
+
+    /// how many implicit conversions are needed to make the type satisfy the filter
+    ///
+    /// branches which need fewer conversions are preferred, see [`DeclareGraph::declare_all`]
+    ///
+    /// [`DeclareGraph::declare_all`]: crate::DeclareGraph::declare_all
+    fn conversions(&self, _ty: &Type) -> usize {
+        0
+    }
 }
 
 pub struct CustomFilter<T: Types, Fs, Fe>
@@ -100,6 +109,44 @@ pub mod filters {
         }
     }
 
+    /// like [`TypeEqual`], but types which could be widened to the expected type losslessly
+    /// are accepted too, at the cost of a conversion
+    ///
+    /// see [`TypeDefine::widens_to`]
+    pub struct ConvertibleTo<'t> {
+        expect: &'t TypeDefine,
+        at: Span,
+    }
+
+    impl<'t> ConvertibleTo<'t> {
+        pub fn new(expect: &'t TypeDefine, at: Span) -> Self {
+            Self { expect, at }
+        }
+    }
+
+    impl WithSpan for ConvertibleTo<'_> {
+        fn get_span(&self) -> Span {
+            self.at
+        }
+    }
+
+    impl BranchFilter<Directly> for ConvertibleTo<'_> {
+        fn satisfy(&self, ty: &Type) -> bool {
+            ty.get_type() == self.expect || ty.get_type().widens_to(self.expect)
+        }
+
+        fn expect(&self, _: &Defs) -> String {
+            format!(
+                "a val whose type is {} or could be widened to it",
+                self.expect
+            )
+        }
+
+        fn conversions(&self, ty: &Type) -> usize {
+            ty.get_type().widens_to(self.expect) as usize
+        }
+    }
+
     pub struct FnParamLen<'n> {
         name: Option<&'n str>,
         expect: usize,
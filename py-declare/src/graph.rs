@@ -13,6 +13,8 @@ pub struct DeclareGraph {
     /// if any of them is impossible, the [`Branch`] will be removed, too
     pub(crate) deps: HashMap<Branch, HashSet<Branch>>,
     pub(crate) rdeps: HashMap<Branch, HashSet<Branch>>,
+    /// groups which have been merged, they are merged again if they lose branches later
+    pub(crate) merged: Vec<(Span, GroupIdx, GroupIdx)>,
 }
 
 impl DeclareGraph {
@@ -51,11 +53,33 @@ impl DeclareGraph {
         self.new_group_inner(at, Default::default(), DeclareState::from_iter(items))
     }
 
+    /// create a group without any branch, its branches will be given by the first group it meets
+    /// (by [`DeclareGraph::merge_group`], [`DeclareGraph::declare_type`] or
+    /// [`DeclareGraph::instantiate`]), and only types which are accepted by `accept` are kept
+    ///
+    /// for example, the null pointer literal could be any pointer type
+    pub fn new_placeholder_group(&mut self, at: terl::Span, accept: Placeholder) -> GroupIdx {
+        let gidx = self.new_group_inner(at, Default::default(), DeclareState::Empty);
+        self[gidx].set_placeholder(accept);
+        gidx
+    }
+
+    /// give a placeholder group its candidates
+    ///
+    /// do nothing if the group is not a placeholder
+    pub fn instantiate<I>(&mut self, gidx: GroupIdx, candidates: I)
+    where
+        I: IntoIterator<Item = Type>,
+    {
+        self[gidx].instantiate(candidates)
+    }
+
     pub fn build_group(&mut self, gb: GroupBuilder) -> GroupIdx {
         let gidx = GroupIdx::new(self.groups.len());
 
         let mut alives = HashMap::new();
         let mut failds = HashMap::new();
+        let mut costs = HashMap::new();
 
         #[derive(Debug)]
         enum BranchMark {
@@ -88,10 +112,13 @@ impl DeclareGraph {
                     alives.insert(new_branch.branch_idx, ty.clone());
                 }
                 Ok(branch_depends) => {
-                    for branch_depends in branch_depends {
+                    for (branch_depends, cost) in branch_depends {
                         let new_branch = Branch::new(gidx, alives.len() + failds.len());
                         self.insert_depends(new_branch, branch_depends);
                         alives.insert(new_branch.branch_idx, ty.clone());
+                        if cost != 0 {
+                            costs.insert(new_branch.branch_idx, cost);
+                        }
                     }
                 }
                 Err(group_errors) => {
@@ -106,6 +133,8 @@ impl DeclareGraph {
         }
 
         let new_group = self.new_group_inner(gb.span, failds, alives.into());
+        self[new_group].costs = costs;
+        self[new_group].default = gb.default;
 
         for (group, mut branch_marks) in used_branches {
             // use hashmap avoiding remove same branch more than once
@@ -150,6 +179,24 @@ impl DeclareGraph {
     }
 
     pub fn merge_group(&mut self, at: terl::Span, base: GroupIdx, from: GroupIdx) {
+        // a placeholder takes all types of the other side
+        for (placeholder, other) in [(base, from), (from, base)] {
+            if self[placeholder].is_placeholder() && !self[other].is_placeholder() {
+                let candidates = self[other].alives(|alives| {
+                    alives
+                        .map(|(_, ty)| Type::from(ty.get_type().clone()))
+                        .collect::<Vec<_>>()
+                });
+                self.instantiate(placeholder, candidates);
+            }
+        }
+
+        self.merged.push((at, base, from));
+        self.intersect(at, base, from);
+    }
+
+    /// keep only the branches whose types exist in both groups
+    fn intersect(&mut self, at: terl::Span, base: GroupIdx, from: GroupIdx) {
         let bases = self[from].alives(|alives| {
             alives
                 .map(|(branch, ty)| (branch, ty.get_type()))
@@ -175,23 +222,29 @@ impl DeclareGraph {
         let (base_kept, from_kept): (HashSet<_>, HashSet<_>) =
             merge.iter().map(|(base, from, _)| (*base, *from)).unzip();
 
+        // TODO: improve error message here
+        let remove_reason = DeclareError::Filtered.with_location(at).into_shared();
+        // a literal doesnt fit in the type explains why the type is removed much better
+        let reason =
+            |this: &Self, other: GroupIdx, ty: &TypeDefine| match this[other].out_of_range(ty) {
+                Some(out_of_range) => out_of_range.clone().into_shared(),
+                None => remove_reason.clone(),
+            };
+
         let removed = bases
             .iter()
-            .map(|(branch, ..)| *branch)
-            .filter(|branch| !from_kept.contains(branch))
+            .filter(|(branch, ..)| !from_kept.contains(branch))
+            .map(|&(branch, ty)| (branch, reason(self, base, ty)))
             .chain(
                 exists
                     .iter()
-                    .map(|(branch, ..)| *branch)
-                    .filter(|branch| !base_kept.contains(branch)),
+                    .filter(|(branch, ..)| !base_kept.contains(branch))
+                    .map(|&(branch, ty)| (branch, reason(self, from, ty))),
             )
             .collect::<Vec<_>>();
 
-        // TODO: improve error message here
-        let remove_reason = DeclareError::Filtered.with_location(at).into_shared();
-
-        for remove in removed {
-            self.remove_branch(remove, remove_reason.clone());
+        for (remove, reason) in removed {
+            self.remove_branch(remove, reason);
         }
     }
 
@@ -201,14 +254,17 @@ impl DeclareGraph {
     /// or non of [`Branch`] match the given type
     pub fn declare_type(&mut self, at: terl::Span, gidx: GroupIdx, expect_ty: &TypeDefine) {
         let group = &mut self[gidx];
+        group.instantiate([Type::from(expect_ty.clone())]);
         // TODO: unknown type support
 
-        let reason = || {
-            DeclareError::Unexpect {
+        let out_of_range = group.out_of_range(expect_ty).cloned();
+        let reason = || match out_of_range {
+            Some(out_of_range) => out_of_range.into_shared(),
+            None => DeclareError::Unexpect {
                 expect: expect_ty.to_string(),
             }
             .with_location(at)
-            .into_shared()
+            .into_shared(),
         };
         for (branch, remove) in group.remove_branches(|_, ty| ty.get_type() != expect_ty, reason) {
             self.remove_branch(branch, remove);
@@ -258,11 +314,97 @@ impl DeclareGraph {
         }
     }
 
+    /// keep only the branches which need the fewest implicit conversions in each group
+    ///
+    /// groups are visited in the order they are built, so arguments are picked before calls
+    /// which use them
+    fn pick_cheapest(&mut self) {
+        for idx in 0..self.groups.len() {
+            let group = &self.groups[idx];
+            let costs = group.alives(|alives| {
+                alives
+                    .map(|(branch, _)| (branch, group.cost(branch.branch_idx)))
+                    .collect::<Vec<_>>()
+            });
+            let Some(cheapest) = costs.iter().map(|(_, cost)| *cost).min() else {
+                continue;
+            };
+            let location = group.get_span();
+
+            for (branch, this) in costs {
+                // the branch may have been removed with the branches it depends on
+                if this > cheapest && self[branch.belong_to].is_alive(branch.branch_idx) {
+                    let reason = DeclareError::MoreConversions { this, cheapest }
+                        .with_location(location)
+                        .into_shared();
+                    self.remove_branch(branch, reason);
+                }
+            }
+        }
+    }
+
+    /// declare groups which are still ambiguous as their default types, if they could be
+    ///
+    /// groups are visited in the order they are built, and branches which depend on the removed
+    /// branches are removed too, so a call is decided by the literals passed to it
+    fn apply_defaults(&mut self) {
+        for idx in 0..self.groups.len() {
+            let group = &self.groups[idx];
+            let Some(default) = group.default.clone() else {
+                continue;
+            };
+            if group.is_declared() || !group.could_be(&default) {
+                continue;
+            }
+
+            let location = group.get_span();
+            let reason = || {
+                DeclareError::Defaulted {
+                    default: default.clone(),
+                }
+                .with_location(location)
+                .into_shared()
+            };
+            let removed =
+                self.groups[idx].remove_branches(|_, ty| ty.get_type() != &default, reason);
+            for (branch, reason) in removed {
+                self.remove_branch(branch, reason);
+            }
+            self.remerge();
+        }
+    }
+
+    /// merge groups which have been merged again until nothing changes, so that branches
+    /// removed after merging are removed from the other side too
+    fn remerge(&mut self) {
+        let alives = |this: &Self| {
+            this.groups
+                .iter()
+                .map(|group| group.alives(|alives| alives.count()))
+                .sum::<usize>()
+        };
+        loop {
+            let previous = alives(self);
+            for (at, base, from) in self.merged.clone() {
+                self.intersect(at, base, from);
+            }
+            if alives(self) == previous {
+                break;
+            }
+        }
+    }
+
+    /// pick the branches which need the fewest implicit conversions, declare ambiguous groups as
+    /// their default types, and then check if all groups are declared
     pub fn declare_all(&mut self) -> Result<(), Vec<terl::Error>> {
+        self.pick_cheapest();
+        self.apply_defaults();
+
         let mut errors = vec![];
         for group in &self.groups {
-            // un-declared group
-            if !group.is_declared() {
+            // un-declared group, groups which are ambiguous because of their dependencies are
+            // not reported again
+            if !group.is_declared() && !group.only_depends_ambiguous() {
                 errors.push(group.make_error());
             }
         }
@@ -1,4 +1,5 @@
 use super::*;
+use py_ir::types::TypeDefine;
 use std::{cell::LazyCell, collections::HashMap};
 use terl::{Span, WithSpan};
 
@@ -79,12 +80,27 @@ impl FromIterator<(usize, Type)> for DeclareState {
     }
 }
 
+/// decide which types a placeholder group accepts
+///
+/// see [`DeclareGraph::new_placeholder_group`]
+#[derive(Debug, Clone, Copy)]
+pub struct Placeholder {
+    /// what the placeholder could be, used in error messages
+    pub expect: &'static str,
+    pub accept: fn(&TypeDefine) -> bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct DeclareGroup {
     span: Span,
     group: GroupIdx,
     failds: HashMap<usize, DeclareError>,
     status: DeclareState,
+    placeholder: Option<Placeholder>,
+    /// implicit conversions needed by branches, branches which are not in it need none
+    pub(crate) costs: HashMap<usize, usize>,
+    /// the type picked if nothing tells which type the group should be
+    pub(crate) default: Option<TypeDefine>,
 }
 
 impl DeclareGroup {
@@ -99,13 +115,61 @@ impl DeclareGroup {
             group,
             failds: fails,
             status,
+            placeholder: None,
+            costs: HashMap::new(),
+            default: None,
         }
     }
 
+    pub(crate) fn set_placeholder(&mut self, placeholder: Placeholder) {
+        self.placeholder = Some(placeholder);
+    }
+
+    /// Returns `true` if the group is a placeholder which has not been instantiated yet
+    pub fn is_placeholder(&self) -> bool {
+        self.placeholder.is_some()
+    }
+
+    /// fill a placeholder group with the candidates it accepts
+    ///
+    /// do nothing if the group is not a placeholder(or has been instantiated)
+    pub fn instantiate<I>(&mut self, candidates: I)
+    where
+        I: IntoIterator<Item = Type>,
+    {
+        let Some(placeholder) = self.placeholder.take() else {
+            return;
+        };
+        let mut accepted: Vec<Type> = vec![];
+        let mut rejected: Vec<Type> = vec![];
+        for candidate in candidates {
+            let ty = candidate.get_type();
+            if !(placeholder.accept)(ty) {
+                rejected.push(candidate);
+            } else if accepted.iter().all(|exist| exist.get_type() != ty) {
+                accepted.push(candidate);
+            }
+        }
+        for (idx, candidate) in rejected.into_iter().enumerate() {
+            let reason = DeclareError::Unexpect {
+                expect: placeholder.expect.to_owned(),
+            };
+            self.push_error(accepted.len() + idx, reason.with_previous(candidate));
+        }
+        self.status = DeclareState::from_iter(accepted);
+    }
+
     pub fn push_error(&mut self, at: usize, error: DeclareError) {
         self.failds.insert(at, error);
     }
 
+    /// return the reason if a literal in this group has been found not fitting the type
+    pub fn out_of_range(&self, ty: &TypeDefine) -> Option<&DeclareError> {
+        self.failds
+            .values()
+            .find(|faild| faild.out_of_range() == Some(ty))
+    }
+
     fn update_state<U>(&mut self, updater: U)
     where
         U: FnOnce(DeclareState) -> DeclareState,
@@ -154,15 +218,21 @@ impl DeclareGroup {
                     new_reason = reason.with_previous(previous);
                     DeclareState::Empty
                 } else {
+                    new_reason = reason;
                     DeclareState::Declared(unique, previous)
                 }
             }
             DeclareState::Declaring(mut items) => {
-                let previous = items.remove(&branch);
-                new_reason = reason.with_previous(previous.unwrap_or_else(|| unreachable!()));
+                match items.remove(&branch) {
+                    Some(previous) => new_reason = reason.with_previous(previous),
+                    None => new_reason = reason,
+                }
                 items.into()
             }
-            _ => unreachable!(),
+            DeclareState::Empty => {
+                new_reason = reason;
+                DeclareState::Empty
+            }
         });
         new_reason
     }
@@ -202,6 +272,43 @@ impl DeclareGroup {
         matches!(self.status, DeclareState::Declared(..))
     }
 
+    /// Returns `true` if the group has more than one branch, but all of them are the same
+    /// type(or overload)
+    ///
+    /// that means the group is undeclared because one of groups it depends on is
+    pub fn only_depends_ambiguous(&self) -> bool {
+        match &self.status {
+            DeclareState::Declaring(alives) => {
+                let mut types = alives.values();
+                let first = types.next();
+                types.all(|ty| first.is_some_and(|first| first.is_same(ty)))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if one of alive branches is the type
+    pub fn could_be(&self, ty: &TypeDefine) -> bool {
+        match &self.status {
+            DeclareState::Empty => false,
+            DeclareState::Declared(_, alive) => alive.get_type() == ty,
+            DeclareState::Declaring(alives) => alives.values().any(|alive| alive.get_type() == ty),
+        }
+    }
+
+    pub fn is_alive(&self, branch: usize) -> bool {
+        match &self.status {
+            DeclareState::Empty => false,
+            DeclareState::Declared(unique, _) => *unique == branch,
+            DeclareState::Declaring(alives) => alives.contains_key(&branch),
+        }
+    }
+
+    /// implicit conversions the branch needs
+    pub fn cost(&self, branch: usize) -> usize {
+        self.costs.get(&branch).copied().unwrap_or_default()
+    }
+
     /// return declare result
     ///
     /// # Panic
@@ -216,18 +323,66 @@ impl DeclareGroup {
 
     pub fn make_error(&self) -> terl::Error {
         let mut err = <Self as terl::WithSpan>::make_error(self, "cant infer type");
+        if self.is_placeholder() {
+            err += "nothing around this tells which type it should be";
+            return err;
+        }
         match &self.status {
             DeclareState::Empty => err += "this cant be declared as any type!",
             DeclareState::Declaring(alives) => {
-                err += "this cant be declared as:";
-                for alives in alives.values() {
-                    err += format!("\t{alives}")
+                // in the order they were given, so the output is stable
+                let mut candidates = alives.iter().collect::<Vec<_>>();
+                candidates.sort_by_key(|(idx, _)| **idx);
+                let mut candidates = candidates
+                    .into_iter()
+                    .map(|(_, candidate)| candidate.to_string())
+                    .collect::<Vec<_>>();
+                // branches of an overload differ in arguments only
+                candidates.dedup();
+
+                if alives
+                    .values()
+                    .all(|alive| matches!(alive, Type::Overload(..)))
+                {
+                    err += "this call is ambiguous, it could call any of:";
+                    for candidate in candidates {
+                        err += format!("\t{candidate}")
+                    }
+                    err += "they need as many implicit conversions as each other, pass arguments \
+                            whose types are exactly the parameters' to tell which one to call";
+                } else {
+                    err += "type is ambiguous, it could be any of:";
+                    for candidate in candidates {
+                        err += format!("\t{candidate}")
+                    }
+                    err += "give it a type explicitly to tell which one it should be";
                 }
+                return err;
             }
             DeclareState::Declared(_, _) => unreachable!(),
         }
 
-        err.extend(self.failds.values().flat_map(|faild| faild.generate()));
+        let mut failds = self.failds.iter().collect::<Vec<_>>();
+        failds.sort_by_key(|(idx, _)| **idx);
+        // literals which dont fit in types are the root causes, other reasons are just noise
+        //
+        // if the literal is required to be some types, only the types which are required
+        // are reported
+        let literal = |faild: &DeclareError| matches!(faild, DeclareError::OutOfRange { .. });
+        let required = failds
+            .iter()
+            .filter(|(_, faild)| !literal(faild))
+            .filter_map(|(_, faild)| faild.out_of_range())
+            .collect::<Vec<_>>();
+        if failds.iter().any(|(_, faild)| literal(faild)) {
+            failds.retain(|(_, faild)| {
+                literal(faild)
+                    && (required.is_empty() || required.contains(&faild.out_of_range().unwrap()))
+            });
+        } else if !required.is_empty() {
+            failds.retain(|(_, faild)| faild.out_of_range().is_some());
+        }
+        err.extend(failds.into_iter().flat_map(|(_, faild)| faild.generate()));
         err
     }
 
@@ -258,11 +413,24 @@ impl WithSpan for DeclareGroup {
 pub struct GroupBuilder {
     pub(crate) span: Span,
     pub(crate) branches: Vec<BranchesBuilder>,
+    pub(crate) default: Option<TypeDefine>,
 }
 
 impl GroupBuilder {
     pub fn new(span: Span, branches: Vec<BranchesBuilder>) -> GroupBuilder {
-        GroupBuilder { span, branches }
+        GroupBuilder {
+            span,
+            branches,
+            default: None,
+        }
+    }
+
+    /// pick the type if the group is still ambiguous after all, like literals
+    ///
+    /// see [`DeclareGraph::declare_all`]
+    pub fn with_default(mut self, default: Option<TypeDefine>) -> Self {
+        self.default = default;
+        self
     }
 }
 
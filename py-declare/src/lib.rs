@@ -144,4 +144,165 @@ mod tests {
 
         assert!(map.declare_all().is_ok());
     }
+
+    #[test]
+    fn out_of_range() {
+        use py_ir::{types::PrimitiveType, value::Literal};
+
+        let span = terl::Span::new(0, 0);
+        let mut map = DeclareGraph::new();
+
+        let literal = |map: &mut DeclareGraph, int: u128, negative: bool| {
+            let branches = mir::Undeclared::literal_branches(&Literal::Integer(int), negative);
+            map.build_group(GroupBuilder::new(span, branches))
+        };
+
+        let min = literal(&mut map, 128, true);
+        map.declare_type(span, min, &PrimitiveType::I8.into());
+        assert!(map[min].is_declared());
+
+        let over = literal(&mut map, 128, false);
+        map.declare_type(span, over, &PrimitiveType::I8.into());
+        let error = format!("{:?}", map.declare_all().unwrap_err());
+        assert!(error.contains("128 does not fit in i8"));
+    }
+
+    #[test]
+    fn ambiguous() {
+        use py_ir::{types::PrimitiveType, value::Literal};
+
+        let span = terl::Span::new(0, 0);
+        let mut map = DeclareGraph::new();
+
+        let branches = mir::Undeclared::literal_branches(&Literal::Integer(1), false);
+        let literal = map.build_group(GroupBuilder::new(span, branches));
+        let two = map.new_static_group(span, [PrimitiveType::U8.into(), PrimitiveType::I64.into()]);
+        map.merge_group(span, two, literal);
+
+        let error = format!("{:?}", map.declare_all().unwrap_err());
+        assert!(error.contains("type is ambiguous"));
+        let (i64, u8) = (error.find(r"\ti64").unwrap(), error.find(r"\tu8").unwrap());
+        assert!(u8 < i64);
+        // types which have been filtered are not the reason of ambiguity
+        assert!(!error.contains("guessed as"));
+    }
+
+    #[test]
+    fn depend_on_many_branches() {
+        use py_ir::types::PrimitiveType;
+
+        let span = terl::Span::new(0, 0);
+        let mut map = DeclareGraph::new();
+        let defs = Defs::new();
+
+        let types = [PrimitiveType::I8, PrimitiveType::I16, PrimitiveType::I32];
+        let many = map.new_static_group(span, types.map(Type::from));
+        let filter = CustomFilter::<Directly, _, _>::new(|_| true, |_| String::new(), span);
+        let branch = BranchesBuilder::new(PrimitiveType::I64.into())
+            .new_depend::<Directly, _>(&mut map, &defs, many, &filter);
+        let group = map.build_group(GroupBuilder::new(span, vec![branch]));
+
+        // one branch for each branch it depends on
+        assert_eq!(map[group].alives(|alives| alives.count()), types.len());
+    }
+
+    #[test]
+    fn cheapest_overload() {
+        use py_ir::types::{PrimitiveType, TypeDefine};
+
+        let span = terl::Span::new(0, 0);
+        let mut map = DeclareGraph::new();
+        let mut defs = Defs::new();
+
+        let mut new_fn = |name: &str, param: PrimitiveType| {
+            let params = vec![defs::Parameter {
+                name: "x".to_owned(),
+                ty: param.into(),
+            }];
+            let sign = defs::FnSign::new(PrimitiveType::I64.into(), params, span, span);
+            defs.new_fn("f", name, sign)
+        };
+        let overloads = [
+            new_fn("f i32", PrimitiveType::I32),
+            new_fn("f i64", PrimitiveType::I64),
+        ];
+
+        let call = |map: &mut DeclareGraph, arg: PrimitiveType| {
+            let arg = map.new_static_group(span, [arg.into()]);
+            let branches = overloads
+                .iter()
+                .map(|overload| {
+                    let param: &TypeDefine = &overload.overload().params[0].ty;
+                    let filter = filters::ConvertibleTo::new(param, span);
+                    BranchesBuilder::new(overload.clone())
+                        .new_depend::<Directly, _>(map, &defs, arg, &filter)
+                })
+                .collect();
+            map.build_group(GroupBuilder::new(span, branches))
+        };
+
+        // i32 could be widened to i64, but the overload which needs no conversion is picked
+        let i32_call = call(&mut map, PrimitiveType::I32);
+        let i64_call = call(&mut map, PrimitiveType::I64);
+        assert!(map.declare_all().is_ok());
+        assert_eq!(map[i32_call].result().overload().name, "f i32");
+        assert_eq!(map[i64_call].result().overload().name, "f i64");
+
+        // i16 needs a conversion for both
+        let tie = call(&mut map, PrimitiveType::I16);
+        let error = format!("{:?}", map.declare_all().unwrap_err());
+        assert!(!map[tie].is_declared());
+        assert!(error.contains("this call is ambiguous"));
+        assert!(error.contains(r"\tf(i32) -> i64") && error.contains(r"\tf(i64) -> i64"));
+    }
+
+    #[test]
+    fn overload_display() {
+        use py_ir::types::PrimitiveType;
+
+        let span = terl::Span::new(0, 0);
+        let mut defs = Defs::new();
+        let params = [PrimitiveType::I32, PrimitiveType::U8, PrimitiveType::F64]
+            .map(|ty| defs::Parameter {
+                name: "x".to_owned(),
+                ty: ty.into(),
+            })
+            .to_vec();
+        let sign = defs::FnSign::new(PrimitiveType::Bool.into(), params, span, span);
+        let overload = defs.new_fn("jia", "_Z3jiaihd", sign);
+
+        // the mangled name is not parsed, so any mangler works
+        let display = overload.overload().to_string();
+        assert_eq!(display, "jia(i32, u8, f64) -> bool");
+    }
+
+    #[test]
+    fn literal_default() {
+        use py_ir::types::PrimitiveType::{self, *};
+        use py_ir::value::Literal;
+
+        let span = terl::Span::new(0, 0);
+        let mut map = DeclareGraph::new();
+
+        let literal = |map: &mut DeclareGraph, literal: Literal, could_be: [PrimitiveType; 2]| {
+            let branches = mir::Undeclared::literal_branches(&literal, false);
+            let default = mir::Undeclared::literal_default(&literal);
+            let builder = GroupBuilder::new(span, branches).with_default(default);
+            let literal = map.build_group(builder);
+            let could_be = map.new_static_group(span, could_be.map(Type::from));
+            map.merge_group(span, could_be, literal);
+            literal
+        };
+
+        let int = literal(&mut map, Literal::Integer(1), [I32, I64]);
+        let float = literal(&mut map, Literal::Float(1.0), [F32, F64]);
+        assert!(map.declare_all().is_ok());
+        assert_eq!(map.get_type(int).as_primitive(), Some(&I64));
+        assert_eq!(map.get_type(float).as_primitive(), Some(&F64));
+
+        // the default one is impossible
+        literal(&mut map, Literal::Integer(1), [I32, U8]);
+        let error = format!("{:?}", map.declare_all().unwrap_err());
+        assert!(error.contains("type is ambiguous"));
+    }
 }
@@ -4,7 +4,7 @@ pub trait IntoIR {
 }
 
 pub mod mir_variable {
-    use crate::{branches, BranchesBuilder, DeclareGraph, GroupIdx};
+    use crate::{branches, BranchesBuilder, DeclareError, DeclareGraph, GroupIdx};
     use py_ir as ir;
     use py_ir::value::Literal;
     use py_lex::ops::Operators;
@@ -36,24 +36,57 @@ pub mod mir_variable {
     }
 
     impl Undeclared<Value> {
-        pub fn literal_branches(var: &Literal) -> Vec<BranchesBuilder> {
+        /// the type a literal is declared as if nothing tells which type it should be
+        ///
+        /// integers default to `i64`, and floats default to `f64`
+        pub fn literal_default(var: &Literal) -> Option<ir::types::TypeDefine> {
             use py_ir::types::PrimitiveType;
             match var {
-                Literal::Char(_) => branches! {() =>  PrimitiveType::char()},
-                // String: greatly in processing...
-                Literal::Integer(_) => branches! {
-                    () =>  PrimitiveType::U8, () =>  PrimitiveType::U16,
-                    () =>  PrimitiveType::U32,() =>  PrimitiveType::U64,
-                    () =>  PrimitiveType::U128,() =>  PrimitiveType::Usize,
-                    () =>  PrimitiveType::I8, () =>  PrimitiveType::I16,
-                    () =>  PrimitiveType::I32,() =>  PrimitiveType::I64,
-                    () =>  PrimitiveType::I128,() =>  PrimitiveType::Isize
+                Literal::Integer(_) => Some(PrimitiveType::I64.into()),
+                Literal::Float(_) => Some(PrimitiveType::F64.into()),
+                _ => None,
+            }
+        }
 
-                },
+        /// `negative` means the literal is negated immediately(like `fu4 128`), its only used
+        /// to check if an integer fits in types
+        pub fn literal_branches(var: &Literal, negative: bool) -> Vec<BranchesBuilder> {
+            use py_ir::types::{ComplexType, PrimitiveType};
+            match var {
+                Literal::Bool(_) => branches! {() =>  PrimitiveType::Bool},
+                Literal::Char(_) => branches! {() =>  PrimitiveType::char()},
+                Literal::String(_) => {
+                    branches! {() => py_ir::types::TypeDefine::from(ComplexType::string())}
+                }
+                Literal::Integer(int) => {
+                    use PrimitiveType::*;
+                    let value = if negative {
+                        format!("-{int}")
+                    } else {
+                        int.to_string()
+                    };
+                    [
+                        U8, U16, U32, U64, U128, Usize, I8, I16, I32, I64, I128, Isize,
+                    ]
+                    .into_iter()
+                    .map(|ty| {
+                        if ty.holds(*int, negative) {
+                            BranchesBuilder::new(ty.into())
+                        } else {
+                            let value = value.clone();
+                            let ty = ty.into();
+                            BranchesBuilder::failed(DeclareError::OutOfRange { value, ty })
+                        }
+                    })
+                    .collect()
+                }
                 Literal::Float(_) => branches! {
                     () =>  PrimitiveType::F32,
                     () =>  PrimitiveType::F64
                 },
+                Literal::Null => {
+                    unreachable!("null can be any pointer type, use a placeholder group instead")
+                }
             }
         }
     }
@@ -63,6 +96,7 @@ pub mod mir_variable {
         Value(Value),
         FnCall(FnCall),
         Operate(Operate),
+        Select(Box<py_ir::Select<Undeclared<Value>>>),
     }
 
     impl From<Value> for AssignValue {
@@ -83,6 +117,12 @@ pub mod mir_variable {
         }
     }
 
+    impl From<py_ir::Select<Undeclared<Value>>> for AssignValue {
+        fn from(v: py_ir::Select<Undeclared<Value>>) -> Self {
+            Self::Select(Box::new(v))
+        }
+    }
+
     #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
     pub enum Operate {
         Unary(Operators, Undeclared<Value>),
@@ -97,18 +137,27 @@ pub mod mir_variable {
     impl py_ir::IRValue for Undeclared<Value> {
         type AssignValue = Undeclared<AssignValue>;
         type VarDefineType = GroupIdx;
-        type FnDefineType = ir::types::TypeDefine;
+        /// the return type could be inferred from `fan3` statements
+        type FnDefineType = GroupIdx;
         type ParameterType = ir::types::TypeDefine;
     }
 
+    impl py_ir::cfg::ConstBool for Undeclared<Value> {
+        fn const_bool(&self) -> Option<bool> {
+            match self.val {
+                Value::Literal(Literal::Bool(bool)) => Some(bool),
+                _ => None,
+            }
+        }
+    }
+
     impl IntoIR for Undeclared<Value> {
         type Forward = ir::value::Value;
 
         fn into_ir(self, map: &crate::DeclareGraph) -> Self::Forward {
             match self.val {
                 Value::Literal(literal) => {
-                    let ty = *map.get_type(self.ty).as_primitive().unwrap();
-                    ir::value::Value::Literal(literal, ty)
+                    ir::value::Value::Literal(literal, map.get_type(self.ty).clone())
                 }
                 Value::Variable(variable) => ir::value::Value::Variable(variable),
             }
@@ -139,6 +188,7 @@ pub mod mir_variable {
                     };
                     (operate, ty).into()
                 }
+                AssignValue::Select(select) => select.into_ir(map).into(),
             }
         }
     }
@@ -179,7 +229,7 @@ mod into_ir_impls {
         fn into_ir(self, map: &DeclareGraph) -> Self::Forward {
             FnDefine {
                 export: self.export,
-                ty: self.ty,
+                ty: map.get_type(self.ty).clone(),
                 name: self.name,
                 params: self.params,
                 body: self.body.into_ir(map),
@@ -191,17 +241,71 @@ mod into_ir_impls {
         type Forward = Statements<Value>;
 
         fn into_ir(self, map: &DeclareGraph) -> Self::Forward {
+            let mut stmts = vec![];
+            for stmt in self.stmts {
+                let converts = match &stmt {
+                    Statement::VarDefine(var_define) => convert_args(var_define, map).collect(),
+                    _ => vec![],
+                };
+                let mut stmt = stmt.into_ir(map);
+                if let Statement::VarDefine(VarDefine {
+                    init: Some(value::AssignValue::FnCall(fn_call)),
+                    ..
+                }) = &mut stmt
+                {
+                    for (nth, convert) in &converts {
+                        fn_call.args[*nth] = Value::Variable(convert.name.clone());
+                    }
+                }
+                stmts.extend(converts.into_iter().map(|(_, convert)| convert.into()));
+                stmts.push(stmt);
+            }
             Statements {
-                stmts: self
-                    .stmts
-                    .into_iter()
-                    .map(|stmt| stmt.into_ir(map))
-                    .collect(),
+                stmts,
                 returned: self.returned,
             }
         }
     }
 
+    /// arguments of the call whose types are not the same as the parameters', they are
+    /// converted into temps before the call
+    fn convert_args<'a>(
+        var_define: &'a VarDefine<MirVariable>,
+        map: &'a DeclareGraph,
+    ) -> impl Iterator<Item = (usize, VarDefine<Value>)> + 'a {
+        let args = match &var_define.init {
+            Some(Undeclared {
+                val: super::AssignValue::FnCall(fn_call),
+                ty,
+            }) => {
+                let params = &map[*ty].result().overload().params;
+                fn_call.args.iter().zip(params.iter()).collect()
+            }
+            _ => vec![],
+        };
+        args.into_iter()
+            .enumerate()
+            .filter_map(move |(nth, (arg, param))| {
+                let from = *map.get_type(arg.ty).as_primitive()?;
+                let to = *param.ty.as_primitive()?;
+                if from == to {
+                    return None;
+                }
+                let convert = value::Convert {
+                    val: arg.clone().into_ir(map),
+                    from,
+                    to,
+                };
+                let var_define = VarDefine {
+                    ty: param.ty.clone(),
+                    name: format!("_{}_{nth}", var_define.name),
+                    init: Some(convert.into()),
+                    is_temp: true,
+                };
+                Some((nth, var_define))
+            })
+    }
+
     impl IntoIR for Statement<MirVariable> {
         type Forward = Statement<Value>;
 
@@ -238,6 +342,10 @@ mod into_ir_impls {
             VarStore {
                 name: self.name,
                 val: self.val.into_ir(map),
+                compound: self.compound.map(|compound| Compound {
+                    op: compound.op,
+                    ty: map.get_type(compound.ty).clone(),
+                }),
             }
         }
     }
@@ -253,6 +361,29 @@ mod into_ir_impls {
         }
     }
 
+    impl IntoIR for Select<MirVariable> {
+        type Forward = Select<Value>;
+
+        fn into_ir(self, map: &DeclareGraph) -> Self::Forward {
+            Select {
+                cond: self.cond.into_ir(map),
+                then: self.then.into_ir(map),
+                else_: self.else_.into_ir(map),
+            }
+        }
+    }
+
+    impl IntoIR for SelectArm<MirVariable> {
+        type Forward = SelectArm<Value>;
+
+        fn into_ir(self, map: &DeclareGraph) -> Self::Forward {
+            SelectArm {
+                val: self.val.into_ir(map),
+                compute: self.compute.into_ir(map),
+            }
+        }
+    }
+
     impl IntoIR for IfBranch<MirVariable> {
         type Forward = IfBranch<Value>;
 
@@ -284,7 +415,7 @@ mod into_ir_impls {
 
         fn into_ir(self, map: &DeclareGraph) -> Self::Forward {
             While {
-                cond: self.cond.into_ir(map),
+                cond: self.cond.map(|cond| cond.into_ir(map)),
                 body: self.body.into_ir(map),
             }
         }
@@ -29,6 +29,15 @@ impl Type {
             panic!()
         }
     }
+
+    /// Returns `true` if the two are the same overload, or the same type
+    pub fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Type::Overload(l), Type::Overload(r)) => l.name == r.name,
+            (Type::Directly(l), Type::Directly(r)) => l.0 == r.0,
+            _ => false,
+        }
+    }
 }
 
 impl std::fmt::Display for Type {
@@ -0,0 +1,273 @@
+//! control flow graph of [`Statements`], used to check if the end of a function could be
+//! reached, and which statements could never be executed
+
+use crate::*;
+use std::collections::HashMap;
+
+/// a value whose truth is known without running the program, like the literal `zhen1`
+///
+/// constant conditions decide which edges of the graph exist: `chong2 can1 zhen1 jie2` never
+/// ends, and the body of `ruo4 can1 jia3 jie2` is never executed
+pub trait ConstBool {
+    fn const_bool(&self) -> Option<bool>;
+}
+
+impl ConstBool for value::Value {
+    fn const_bool(&self) -> Option<bool> {
+        match self {
+            value::Value::Literal(value::Literal::Bool(bool), _) => Some(*bool),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct NodeIdx(usize);
+
+/// a node for each statement, and edges for each way the control flow could go
+///
+/// statements in [`Condition::compute`] and [`SelectArm::compute`] are not in the graph,
+/// they never change the control flow
+#[derive(Debug)]
+pub struct ControlFlowGraph<'s, Var: IRValue> {
+    stmts: &'s [Statement<Var>],
+    /// successors of each node
+    nodes: Vec<Vec<NodeIdx>>,
+    /// where each statement is in `nodes`
+    statements: HashMap<*const Statement<Var>, NodeIdx>,
+    /// nodes which could be reached from [`Node::Entry`]
+    reachable: Vec<bool>,
+}
+
+impl<'s, Var: IRValue + ConstBool> ControlFlowGraph<'s, Var> {
+    /// where the statements start
+    const ENTRY: NodeIdx = NodeIdx(0);
+    /// the end of the statements, reaching it means the statements could fall through
+    const END: NodeIdx = NodeIdx(1);
+    /// where `fan3` goes
+    const RETURN: NodeIdx = NodeIdx(2);
+
+    pub fn new(stmts: &'s [Statement<Var>]) -> Self {
+        let mut cfg = Self {
+            stmts,
+            nodes: vec![vec![]; 3],
+            statements: HashMap::new(),
+            reachable: vec![],
+        };
+        let entry = cfg.sequence(stmts, Self::END);
+        cfg.nodes[Self::ENTRY.0].push(entry);
+
+        cfg.reachable = vec![false; cfg.nodes.len()];
+        let mut stack = vec![Self::ENTRY];
+        while let Some(node) = stack.pop() {
+            if !std::mem::replace(&mut cfg.reachable[node.0], true) {
+                stack.extend(&cfg.nodes[node.0]);
+            }
+        }
+        cfg
+    }
+
+    fn new_node(&mut self, succs: Vec<NodeIdx>) -> NodeIdx {
+        self.nodes.push(succs);
+        NodeIdx(self.nodes.len() - 1)
+    }
+
+    /// # Return
+    ///
+    /// the first node of the statements, or `next` if there is no statement
+    fn sequence(&mut self, stmts: &'s [Statement<Var>], next: NodeIdx) -> NodeIdx {
+        stmts
+            .iter()
+            .rev()
+            .fold(next, |next, stmt| self.statement(stmt, next))
+    }
+
+    /// the node of a condition test, after its `compute` is executed. it goes to `then` if the
+    /// condition is true
+    fn test(&mut self, cond: &Condition<Var>, then: NodeIdx, else_: NodeIdx) -> NodeIdx {
+        let succs = match cond.val.const_bool() {
+            Some(true) => vec![then],
+            Some(false) => vec![else_],
+            None => vec![then, else_],
+        };
+        self.new_node(succs)
+    }
+
+    fn statement(&mut self, stmt: &'s Statement<Var>, next: NodeIdx) -> NodeIdx {
+        let succs = match stmt {
+            Statement::VarDefine(_) | Statement::VarStore(_) => vec![next],
+            Statement::Return(_) => vec![Self::RETURN],
+            Statement::Block(stmts) => vec![self.sequence(stmts, next)],
+            Statement::If(if_) => {
+                let else_ = match &if_.else_ {
+                    Some(else_) => self.sequence(else_, next),
+                    None => next,
+                };
+                let first = if_.branches.iter().rev().fold(else_, |else_, branch| {
+                    let body = self.sequence(&branch.body, next);
+                    self.test(&branch.cond, body, else_)
+                });
+                vec![first]
+            }
+            Statement::While(while_) => {
+                // the body goes back to the statement itself, so the node is made first
+                let node = self.new_node(vec![]);
+                self.statements.insert(stmt, node);
+                let body = self.sequence(&while_.body, node);
+                let first = match &while_.cond {
+                    Some(cond) => self.test(cond, body, next),
+                    None => body,
+                };
+                self.nodes[node.0].push(first);
+                return node;
+            }
+        };
+        let node = self.new_node(succs);
+        self.statements.insert(stmt, node);
+        node
+    }
+
+    /// if the end of the statements could be reached, which means there may be no `fan3`
+    pub fn falls_through(&self) -> bool {
+        self.reachable[Self::END.0]
+    }
+
+    /// if the statement could be executed
+    ///
+    /// # Panics
+    ///
+    /// panic if the statement is not in the graph
+    pub fn is_reachable(&self, stmt: &Statement<Var>) -> bool {
+        self.reachable[self.statements[&(stmt as *const _)].0]
+    }
+
+    /// statements which could never be executed, in the order they are written
+    pub fn unreachable(&self) -> Vec<&'s Statement<Var>> {
+        fn walk<'s, Var: IRValue + ConstBool>(
+            cfg: &ControlFlowGraph<'s, Var>,
+            stmts: &'s [Statement<Var>],
+            unreachable: &mut Vec<&'s Statement<Var>>,
+        ) {
+            for stmt in stmts {
+                if !cfg.is_reachable(stmt) {
+                    unreachable.push(stmt);
+                }
+                match stmt {
+                    Statement::Block(stmts) => walk(cfg, stmts, unreachable),
+                    Statement::If(if_) => {
+                        for branch in &if_.branches {
+                            walk(cfg, &branch.body, unreachable);
+                        }
+                        if let Some(else_) = &if_.else_ {
+                            walk(cfg, else_, unreachable);
+                        }
+                    }
+                    Statement::While(while_) => walk(cfg, &while_.body, unreachable),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut unreachable = vec![];
+        walk(self, self.stmts, &mut unreachable);
+        unreachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PrimitiveType;
+    use crate::value::{Literal, Value};
+
+    fn bool(bool: bool) -> Condition<Value> {
+        Condition {
+            val: Value::Literal(Literal::Bool(bool), PrimitiveType::Bool.into()),
+            compute: Statements::new(),
+        }
+    }
+
+    fn unknown() -> Condition<Value> {
+        Condition {
+            val: Value::Variable("x".to_owned()),
+            compute: Statements::new(),
+        }
+    }
+
+    fn ret() -> Statement<Value> {
+        Return { val: None }.into()
+    }
+
+    fn store() -> Statement<Value> {
+        let val = Value::Literal(Literal::Integer(1), PrimitiveType::I64.into());
+        VarStore {
+            name: "x".to_owned(),
+            val,
+            compound: None,
+        }
+        .into()
+    }
+
+    fn if_(cond: Condition<Value>, body: Vec<Statement<Value>>) -> Statement<Value> {
+        If {
+            branches: vec![IfBranch {
+                cond,
+                body: body.into(),
+            }],
+            else_: None,
+        }
+        .into()
+    }
+
+    fn while_(cond: Option<Condition<Value>>, body: Vec<Statement<Value>>) -> Statement<Value> {
+        While {
+            cond,
+            body: body.into(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn returns() {
+        let stmts = [store(), ret(), store()];
+        let cfg = ControlFlowGraph::new(&stmts);
+        assert!(!cfg.falls_through());
+        assert!(cfg.is_reachable(&stmts[1]));
+        assert_eq!(cfg.unreachable().len(), 1);
+        assert!(std::ptr::eq(cfg.unreachable()[0], &stmts[2]));
+
+        let stmts = [if_(unknown(), vec![ret()])];
+        assert!(ControlFlowGraph::new(&stmts).falls_through());
+    }
+
+    #[test]
+    fn infinite_loops() {
+        for cond in [None, Some(bool(true))] {
+            let stmts = [while_(cond, vec![store()]), store()];
+            let cfg = ControlFlowGraph::new(&stmts);
+            assert!(!cfg.falls_through());
+            assert!(!cfg.is_reachable(&stmts[1]));
+        }
+
+        let stmts = [while_(Some(unknown()), vec![store()])];
+        assert!(ControlFlowGraph::new(&stmts).falls_through());
+    }
+
+    #[test]
+    fn dead_branches() {
+        let stmts = [if_(bool(false), vec![store(), ret()])];
+        let cfg = ControlFlowGraph::new(&stmts);
+        assert!(cfg.falls_through());
+        assert_eq!(cfg.unreachable().len(), 2);
+
+        let stmts = [if_(bool(true), vec![ret()]), store()];
+        let cfg = ControlFlowGraph::new(&stmts);
+        assert!(!cfg.falls_through());
+        assert!(!cfg.is_reachable(&stmts[1]));
+
+        let stmts = [while_(Some(bool(false)), vec![store()])];
+        let cfg = ControlFlowGraph::new(&stmts);
+        assert!(cfg.falls_through());
+        assert_eq!(cfg.unreachable().len(), 1);
+    }
+}
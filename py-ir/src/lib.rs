@@ -1,3 +1,4 @@
+pub mod cfg;
 pub mod types;
 pub mod value;
 
@@ -180,9 +181,20 @@ pub struct VarDefine<Var: IRValue> {
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
-pub struct VarStore<Var> {
+pub struct VarStore<Var: IRValue> {
     pub name: String,
     pub val: Var,
+    /// [`Some`] for compound assignments like `x jia1 wei2 1`: the variable is read once,
+    /// computed with `val`, and the result is written back
+    pub compound: Option<Compound<Var>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Compound<Var: IRValue> {
+    pub op: py_lex::ops::Operators,
+    /// the type of the variable
+    #[serde(rename = "type")]
+    pub ty: Var::VarDefineType,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -192,6 +204,23 @@ pub struct Condition<Var: IRValue> {
     pub compute: Statements<Var>,
 }
 
+/// a value chosen by a condition, which comes from conditional expressions like
+/// `ruo4 can1 a da4 b jie2 a ze2 b`
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Select<Var: IRValue> {
+    pub cond: Condition<Var>,
+    pub then: SelectArm<Var>,
+    #[serde(rename = "else")]
+    pub else_: SelectArm<Var>,
+}
+
+/// `compute` is only executed when the arm is chosen
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct SelectArm<Var: IRValue> {
+    pub val: Var,
+    pub compute: Statements<Var>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct IfBranch<Var: IRValue> {
     pub cond: Condition<Var>,
@@ -220,13 +249,15 @@ impl<Var: IRValue> ControlFlow for If<Var> {
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct While<Var: IRValue> {
-    pub cond: Condition<Var>,
+    /// [`None`] means the loop never ends
+    pub cond: Option<Condition<Var>>,
     pub body: Statements<Var>,
 }
 
 impl<Var: IRValue> ControlFlow for While<Var> {
+    /// there is no way to break a loop, so codes after an infinite loop are never reached
     fn returned(&self) -> bool {
-        false
+        self.cond.is_none()
     }
 }
 
@@ -250,7 +281,10 @@ macro_rules! custom_ir_variable {
         $vis type Statement   = $crate::Statement   <$variable>;
         $vis type VarDefine   = $crate::VarDefine   <$variable>;
         $vis type VarStore    = $crate::VarStore    <$variable>;
+        $vis type Compound    = $crate::Compound    <$variable>;
         $vis type Condition   = $crate::Condition   <$variable>;
+        $vis type Select      = $crate::Select      <$variable>;
+        $vis type SelectArm   = $crate::SelectArm   <$variable>;
         $vis type If          = $crate::If          <$variable>;
         $vis type IfBranch    = $crate::IfBranch    <$variable>;
         $vis type While       = $crate::While       <$variable>;
@@ -46,6 +46,53 @@ impl PrimitiveType {
         )
     }
 
+    /// Returns `true` if the integer can be represented by the type
+    ///
+    /// `negative` means the integer is `-int`
+    pub fn holds(&self, int: u128, negative: bool) -> bool {
+        if !self.is_integer() {
+            return false;
+        }
+        let width = self.width() as u32;
+        if self.is_signed() {
+            // the absolute value of min
+            let min = 1u128 << (width - 1);
+            if negative {
+                int <= min
+            } else {
+                int < min
+            }
+        } else {
+            (!negative || int == 0) && (width == 128 || int >> width == 0)
+        }
+    }
+
+    /// Returns `true` if values of the type can be converted to `to` implicitly without losing
+    /// anything
+    ///
+    /// - integers can be widened to wider integers of the same signedness
+    /// - unsigned integers can be widened to wider signed integers
+    /// - integers can be widened to floats whose mantissa holds all of their values
+    /// - `f32` can be widened to `f64`
+    ///
+    /// the width of `usize` and `isize` depends on the target, so they are never widened
+    pub fn widens_to(&self, to: &Self) -> bool {
+        let sized = |ty: &Self| !matches!(ty, Self::Usize | Self::Isize);
+        if self == to || !sized(self) || !sized(to) || self == &Self::Bool {
+            return false;
+        }
+        let (from_width, to_width) = (self.width(), to.width());
+        match (self.is_float(), to.is_float()) {
+            (true, true) => from_width < to_width,
+            (true, false) => false,
+            // mantissa of f32 and f64 have 24 and 53 bits
+            (false, true) => from_width <= if to_width == 32 { 16 } else { 32 },
+            (false, false) if to == &Self::Bool => false,
+            (false, false) if self.is_signed() => to.is_signed() && from_width < to_width,
+            (false, false) => from_width < to_width,
+        }
+    }
+
     pub fn width(&self) -> usize {
         match self {
             PrimitiveType::Bool => 1,
@@ -69,6 +116,34 @@ impl std::fmt::Display for PrimitiveType {
     }
 }
 
+impl std::str::FromStr for PrimitiveType {
+    type Err = ();
+
+    /// the reverse of [`Display`], used to recover primitive types stored in [`ComplexType::ty`]
+    ///
+    /// [`Display`]: std::fmt::Display
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "bool" => Self::Bool,
+            "i8" => Self::I8,
+            "u8" => Self::U8,
+            "i16" => Self::I16,
+            "u16" => Self::U16,
+            "i32" => Self::I32,
+            "u32" => Self::U32,
+            "i64" => Self::I64,
+            "u64" => Self::U64,
+            "i128" => Self::I128,
+            "u128" => Self::U128,
+            "usize" => Self::Usize,
+            "isize" => Self::Isize,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            _ => return Err(()),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TypeDecorators {
     // #[deprecated = "unclear semantics"]
@@ -157,6 +232,14 @@ impl ComplexType {
             ty: "u8".into(),
         }
     }
+
+    /// Returns `true` if the outermost decorator (ignoring `const`) is [`TypeDecorators::Pointer`]
+    pub fn is_pointer(&self) -> bool {
+        self.decorators
+            .iter()
+            .find(|dec| **dec != TypeDecorators::Const)
+            .is_some_and(|dec| *dec == TypeDecorators::Pointer)
+    }
 }
 
 impl std::fmt::Display for ComplexType {
@@ -205,6 +288,23 @@ impl TypeDefine {
             None
         }
     }
+
+    /// Returns `true` if both types are primitive, and `self` could be widened to `to`
+    ///
+    /// see [`PrimitiveType::widens_to`]
+    pub fn widens_to(&self, to: &TypeDefine) -> bool {
+        match (self, to) {
+            (Self::Primitive(from), Self::Primitive(to)) => from.widens_to(to),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the type define is a pointer
+    ///
+    /// see [`ComplexType::is_pointer`]
+    pub fn is_pointer(&self) -> bool {
+        matches!(self, Self::Complex(ty) if ty.is_pointer())
+    }
 }
 
 impl std::fmt::Display for TypeDefine {
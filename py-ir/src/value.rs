@@ -7,7 +7,11 @@ use crate::types::{PrimitiveType, TypeDefine};
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub enum Value {
     Variable(String),
-    Literal(Literal, PrimitiveType),
+    /// the type is always a [`PrimitiveType`], except for [`Literal::Null`], which is a pointer,
+    /// and [`Literal::String`], which is [`ComplexType::string`]
+    ///
+    /// [`ComplexType::string`]: crate::types::ComplexType::string
+    Literal(Literal, TypeDefine),
 }
 
 /// [`Operate::Unary`] and [`Operate::Binary`] are normal operations aroud primitives
@@ -19,11 +23,24 @@ pub enum Operate {
     Binary(Operators, Value, Value),
 }
 
+/// a lossless conversion which is inserted where a value is used as a wider type, like passing
+/// an `i32` to a parameter whose type is `i64`
+///
+/// see [`PrimitiveType::widens_to`]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Convert {
+    pub val: Value,
+    pub from: PrimitiveType,
+    pub to: PrimitiveType,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub enum AssignValue {
     Value(Value),
     FnCall(FnCall<Value>),
     Operate(Operate, PrimitiveType),
+    Select(Box<crate::Select<Value>>),
+    Convert(Convert),
 }
 
 impl From<Value> for AssignValue {
@@ -44,6 +61,18 @@ impl From<(Operate, PrimitiveType)> for AssignValue {
     }
 }
 
+impl From<Convert> for AssignValue {
+    fn from(v: Convert) -> Self {
+        Self::Convert(v)
+    }
+}
+
+impl From<crate::Select<Value>> for AssignValue {
+    fn from(v: crate::Select<Value>) -> Self {
+        Self::Select(Box::new(v))
+    }
+}
+
 impl super::IRValue for Value {
     type AssignValue = AssignValue;
     type VarDefineType = TypeDefine;
@@ -58,26 +87,31 @@ pub struct FnCall<Var> {
     pub args: Vec<Var>,
 }
 
-/// [`Literal::Char`], [`Literal::Integer`] and [`Literal::Float`]
+/// [`Literal::Bool`], [`Literal::Char`], [`Literal::Integer`] and [`Literal::Float`]
 /// mean literals
 ///
-/// althogn [`String`] is also [`Literal`], it will be replaced with [`VarDefine`] statement
-/// so that the type of [`Literal`] can be represented by [`PrimitiveType`]
+/// [`Literal::String`] is a nul-terminated utf-8 string, which is an array of `u8`
 ///
-/// [`VarDefine`]: super
+/// [`Literal::Null`] is a pointer, whose type is decided by where it is used
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub enum Literal {
+    Bool(bool),
     Char(char),
-    Integer(usize),
+    Integer(u128),
     Float(f64),
+    String(String),
+    Null,
 }
 
 impl std::fmt::Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Literal::Bool(b) => f.write_fmt(format_args!("{b}")),
             Literal::Char(ch) => f.write_char(*ch),
             Literal::Integer(nu) => f.write_fmt(format_args!("{nu}")),
             Literal::Float(fl) => f.write_fmt(format_args!("{fl}")),
+            Literal::String(str) => f.write_str(str),
+            Literal::Null => f.write_str("null"),
         }
     }
 }
@@ -1,5 +1,9 @@
 mod macros;
 
+#[cfg(feature = "parse")]
+pub mod pinyin;
+#[cfg(feature = "parse")]
+pub mod suggest;
 #[cfg(feature = "parse")]
 mod token;
 #[cfg(feature = "parse")]
@@ -13,3 +17,17 @@ pub mod preprocess;
 pub mod syntax;
 #[cfg(feature = "types")]
 pub mod types;
+
+/// all keeping keywords in all spellings
+pub fn keywords() -> Vec<&'static str> {
+    let mut keywords = vec![];
+    #[cfg(feature = "ops")]
+    ops::KEPPING_KEYWORDS.with(|keeps| keywords.extend(keeps.iter().copied()));
+    #[cfg(feature = "preprocess")]
+    preprocess::KEPPING_KEYWORDS.with(|keeps| keywords.extend(keeps.iter().copied()));
+    #[cfg(feature = "syntax")]
+    syntax::KEPPING_KEYWORDS.with(|keeps| keywords.extend(keeps.iter().copied()));
+    #[cfg(feature = "types")]
+    types::KEPPING_KEYWORDS.with(|keeps| keywords.extend(keeps.iter().copied()));
+    keywords
+}
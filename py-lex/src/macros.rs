@@ -1,13 +1,16 @@
 /// use to define some keyword
 ///
 /// you should only use at most one keywords! macro in a mod
+///
+/// a keyword could have other spellings(like hanzi), which are written after `|`:
+/// `"ruo4" | "若" -> If,`
 #[macro_export]
 macro_rules! reverse_parse_keywords {
     ($(
         $(#[$metas:meta])*
         keywords $enum_name:ident
         { $(
-            $string:literal -> $var:ident,
+            $string:literal $(| $alias:literal)* -> $var:ident,
         )*}
     )*) => {
         $(
@@ -29,6 +32,15 @@ macro_rules! reverse_parse_keywords {
 
         }
 
+        impl $enum_name {
+            /// other spellings of the keyword, like hanzi
+            pub fn aliases(&self) -> &'static [&'static str] {
+                match self {
+                    $(Self::$var => &[$($alias),*],)*
+                }
+            }
+        }
+
         impl std::fmt::Display for $enum_name {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
                 f.write_str(self)
@@ -45,9 +57,10 @@ macro_rules! reverse_parse_keywords {
                     return p.unmatch(format!("expect {}, but non token left", self))
                 };
 
-                if &**next != &**self {
+                if &**next != &**self && !self.aliases().contains(&&**next) {
                     let msg = format!("expect {}, but {} was got", self, &**next);
-                    return p.unmatch(msg)
+                    let note = $crate::suggest::did_you_mean(&**next, [&**self]);
+                    return p.unmatch(msg).map_err(|e| note.into_iter().fold(e, terl::ParseError::append))
                 }
 
                 Ok(*self)
@@ -62,6 +75,7 @@ macro_rules! reverse_parse_keywords {
                 let mut set = std::collections::HashSet::<&'static str>::default();
                 $($(
                     set.insert($string);
+                    $(set.insert($alias);)*
                 )*)*
                 set
             };
@@ -75,7 +89,7 @@ macro_rules! front_parse_keywords {
         $(#[$metas:meta])*
         keywords $enum_name:ident
         { $(
-            $string:literal -> $var:ident,
+            $string:literal $(| $alias:literal)* -> $var:ident,
         )*}
     )*) => {
         $crate::reverse_parse_keywords! {
@@ -83,7 +97,7 @@ macro_rules! front_parse_keywords {
             $(#[$metas])*
             keywords $enum_name
             { $(
-                $string -> $var,
+                $string $(| $alias)* -> $var,
             )*}
             )*
         }
@@ -92,7 +106,7 @@ macro_rules! front_parse_keywords {
             $(#[$metas])*
             $enum_name
             { $(
-                $string -> $var,
+                $string $(| $alias)* -> $var,
             )*}
         }
         )*
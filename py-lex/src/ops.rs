@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! parse_unit_impl {
     ($enum_name:ident {
-        $($string:literal -> $var:ident,)*
+        $($string:literal $(| $alias:literal)* -> $var:ident,)*
     }) => {
         #[cfg(feature = "parse")]
         impl terl::ParseUnit<$crate::Token> for $enum_name {
@@ -15,12 +15,14 @@ macro_rules! parse_unit_impl {
                     static MAP: HashMap<&'static str, $enum_name> = {
                         let mut map = HashMap::new();
                         $(
-                            if let Some(previous) = map.get($string) {
-                                panic!("conflicting: both `{}` and `{}` are `{}`",
-                                    $enum_name::$var, previous, $string
-                                );
+                            for string in [$string $(, $alias)*] {
+                                if let Some(previous) = map.get(string) {
+                                    panic!("conflicting: both `{}` and `{}` are `{}`",
+                                        $enum_name::$var, previous, string
+                                    );
+                                }
+                                map.insert(string, $enum_name::$var);
                             }
-                            map.insert($string, $enum_name::$var);
                         )*
                         map
                     };
@@ -40,9 +42,9 @@ macro_rules! parse_unit_impl {
                         Ok(item)
                     },
                     None => {
-                        p.unmatch(
-                            format!("{} matched non of {}", &**next , stringify!($enum_name)),
-                        )
+                        let msg = format!("{} matched non of {}", &**next , stringify!($enum_name));
+                        let note = MAP.with(|map| $crate::suggest::did_you_mean(&**next, map.keys().copied()));
+                        p.unmatch(msg).map_err(|e| note.into_iter().fold(e, terl::ParseError::append))
                     }
                 }
             }
@@ -50,12 +52,25 @@ macro_rules! parse_unit_impl {
     };
 }
 
+/// operators are left associative by default
+macro_rules! grouping {
+    () => {
+        Associativity::Left
+    };
+    ($grouping:ident) => {
+        Associativity::$grouping
+    };
+}
+
 macro_rules! operators {
     (
         $(#[$metas:meta])*
         $(
             symbols $sub_class:ident {
-                $($string:literal -> $var:ident : $ass:ident $priority:expr),*
+                $(
+                    $string:literal $(| $alias:literal)* -> $var:ident
+                        : $ass:ident $priority:literal $($grouping:ident)?
+                ),*
             }
         )*
 
@@ -78,6 +93,17 @@ macro_rules! operators {
             }
         }
 
+        /// how binary operators with the same priority are grouped, operators are
+        /// [`Associativity::Left`] unless it is declared after the priority
+        $(#[$metas])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Associativity {
+            /// `a - b - c` is `(a - b) - c`
+            Left,
+            /// `a ** b ** c` is `a ** (b ** c)`
+            Right,
+        }
+
         $(#[$metas])*
         #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         pub enum OperatorTypes {
@@ -88,7 +114,7 @@ macro_rules! operators {
             $(#[$metas])*
             keywords Operators {
                 $(
-                    $($string -> $var,)*
+                    $($string $(| $alias)* -> $var,)*
                 )*
             }
         }
@@ -96,7 +122,7 @@ macro_rules! operators {
         parse_unit_impl!{
             Operators {
                 $(
-                $($string -> $var,)*
+                $($string $(| $alias)* -> $var,)*
                 )*
             }
         }
@@ -132,6 +158,15 @@ macro_rules! operators {
                     )*
                 }
             }
+
+            /// return how the operator is grouped with others which have the same priority
+            pub fn grouping(&self) -> Associativity {
+                match *self {
+                    $(
+                        $(Self::$var => grouping!($($grouping)?),)*
+                    )*
+                }
+            }
         }
 
         pub mod sub_classes {
@@ -140,7 +175,7 @@ macro_rules! operators {
             $crate::reverse_parse_keywords! {
                 $(
                 keywords $sub_class {
-                    $($string -> $var,)*
+                    $($string $(| $alias)* -> $var,)*
                 }
                 )*
             }
@@ -179,6 +214,10 @@ macro_rules! operators {
                         $(Self::$var => $priority,)*
                     }
                 }
+
+                pub fn grouping(&self) -> Associativity {
+                    Operators::from(*self).grouping()
+                }
             }
 
         )*
@@ -189,44 +228,58 @@ macro_rules! operators {
 operators! {
     #[derive(serde::Serialize,serde::Deserialize)]
     symbols AlgebraOperator {
-        "jia1"   -> Add : Binary 6,
-        "jian3"  -> Sub : Binary 6,
-        "cheng2" -> Mul : Binary 5,
-        "chu2"   -> Div : Binary 5,
-        "mo2"    -> Mod : Binary 5,
-        "mi4"    -> Pow : Binary 4,
-        "dui4"   -> Log : Binary 4
+        "jia1"   | "加" -> Add : Binary 6,
+        "jian3"  | "减" -> Sub : Binary 6,
+        "cheng2" | "乘" -> Mul : Binary 5,
+        "chu2"   | "除" -> Div : Binary 5,
+        "mo2"    | "模" -> Mod : Binary 5,
+        "mi4"    | "幂" -> Pow : Binary 4 Right,
+        "dui4"   | "对" -> Log : Binary 4,
+        "fu4"    | "负" -> Neg : Unary  3
     }
     symbols CompareOperator {
-        "tong2"      -> Eq  : Binary 10,
-        "fei1tong2"  -> Neq : Binary 10,
-        "da4"        -> Gt  : Binary 8,
-        "xiao3"      -> Lt  : Binary 8,
-        "da4deng3"   -> Ge  : Binary 8,
-        "xiao3deng3" -> Le  : Binary 8
+        "tong2"      | "同"   -> Eq  : Binary 10,
+        "fei1tong2"  | "非同" -> Neq : Binary 10,
+        "da4"        | "大"   -> Gt  : Binary 8,
+        "xiao3"      | "小"   -> Lt  : Binary 8,
+        "da4deng3"   | "大等" -> Ge  : Binary 8,
+        "xiao3deng3" | "小等" -> Le  : Binary 8
     }
     symbols LogicalOperator {
-        "yu3"  -> And : Binary 14,
-        "huo4" -> Or  : Binary 15,
-        "fei1" -> Not : Unary  3
+        "yu3"  | "与" -> And : Binary 14,
+        "huo4" | "或" -> Or  : Binary 15,
+        "fei1" | "非" -> Not : Unary  3
     }
     symbols ArithmeticOperator {
-        "wei4yu3"     -> Band : Binary 11,
-        "wei4huo4"    -> Bor  : Binary 13,
-        "wei4fei1"    -> Bnot : Unary  3,
-        "wei4yi4huo4" -> Xor  : Binary 12,
-        "zuo3yi2"     -> Shl  : Binary 7,
-        "you4yi2"     -> Shr  : Binary 7
+        "wei4yu3"     | "位与"   -> Band : Binary 11,
+        "wei4huo4"    | "位或"   -> Bor  : Binary 13,
+        "wei4fei1"    | "位非"   -> Bnot : Unary  3,
+        "wei4yi4huo4" | "位异或" -> Xor  : Binary 12,
+        "zuo3yi2"     | "左移"   -> Shl  : Binary 7,
+        "you4yi2"     | "右移"   -> Shr  : Binary 7
     }
     symbols SpecialOperator {
-        "qu3zhi3"   -> AddrOf     : Unary  3,
-        "fang3zhi3" -> Deref      : Unary  3,
-        "fang3su4"  -> GetElement : Binary 2,
-        "zhuan3"    -> Cast       : Unary  2,
-        "chang2du4" -> SizeOf     : Unary  3
+        "qu3zhi3"   | "取址" -> AddrOf     : Unary  3,
+        "fang3zhi3" | "访址" -> Deref      : Unary  3,
+        "fang3su4"  | "访素" -> GetElement : Binary 2,
+        "zhuan3"    | "转"   -> Cast       : Unary  2,
+        "chang2du4" | "长度" -> SizeOf     : Unary  3
     }
     symbols StructOperator {
-        "jie2"      -> BracketL   : None 0,
-        "he2"       -> BracketR   : None 0
+        "jie2"      | "结"   -> BracketL   : None 0,
+        "he2"       | "合"   -> BracketR   : None 0
+    }
+}
+
+impl Operators {
+    /// binary algebra and arithmetic operators could be used in compound assignments, like
+    /// `x jia1 wei2 1`, except `dui4` which is not lowered as a binary operator
+    pub fn is_compound_assignable(&self) -> bool {
+        self.associativity() == OperatorAssociativity::Binary
+            && *self != Operators::Log
+            && matches!(
+                self.op_ty(),
+                OperatorTypes::AlgebraOperator | OperatorTypes::ArithmeticOperator
+            )
     }
 }
@@ -0,0 +1,212 @@
+//! pinyin in keyword tables are numbered, like `ruo4`, but people often type tone marks
+//! (`ruò`) or even no tone at all (`ruo`)
+//!
+//! this module converts them into the numbered form
+
+use std::collections::HashMap;
+
+/// all valid pinyin syllables without tone, `ü` is written as `v`
+const SYLLABLES: &str = "
+    a ai an ang ao
+    ba bai ban bang bao bei ben beng bi bian biao bie bin bing bo bu
+    ca cai can cang cao ce cen ceng cha chai chan chang chao che chen cheng chi chong chou chu
+    chua chuai chuan chuang chui chun chuo ci cong cou cu cuan cui cun cuo
+    da dai dan dang dao de dei den deng di dia dian diao die ding diu dong dou du duan dui dun
+    duo
+    e ei en eng er
+    fa fan fang fei fen feng fo fou fu
+    ga gai gan gang gao ge gei gen geng gong gou gu gua guai guan guang gui gun guo
+    ha hai han hang hao he hei hen heng hong hou hu hua huai huan huang hui hun huo
+    ji jia jian jiang jiao jie jin jing jiong jiu ju juan jue jun
+    ka kai kan kang kao ke kei ken keng kong kou ku kua kuai kuan kuang kui kun kuo
+    la lai lan lang lao le lei leng li lia lian liang liao lie lin ling liu lo long lou lu luan
+    lun luo lv lve
+    ma mai man mang mao me mei men meng mi mian miao mie min ming miu mo mou mu
+    na nai nan nang nao ne nei nen neng ni nian niang niao nie nin ning niu nong nou nu nuan
+    nuo nv nve
+    o ou
+    pa pai pan pang pao pei pen peng pi pian piao pie pin ping po pou pu
+    qi qia qian qiang qiao qie qin qing qiong qiu qu quan que qun
+    ran rang rao re ren reng ri rong rou ru rua ruan rui run ruo
+    sa sai san sang sao se sen seng sha shai shan shang shao she shei shen sheng shi shou shu
+    shua shuai shuan shuang shui shun shuo si song sou su suan sui sun suo
+    ta tai tan tang tao te teng ti tian tiao tie ting tong tou tu tuan tui tun tuo
+    wa wai wan wang wei wen weng wo wu
+    xi xia xian xiang xiao xie xin xing xiong xiu xu xuan xue xun
+    ya yan yang yao ye yi yin ying yo yong you yu yuan yue yun
+    za zai zan zang zao ze zei zen zeng zha zhai zhan zhang zhao zhe zhei zhen zheng zhi zhong
+    zhou zhu zhua zhuai zhuan zhuang zhui zhun zhuo zi zong zou zu zuan zui zun zuo
+";
+
+/// the longest syllable is `chuang` and `shuang` and `zhuang`
+const LONGEST_SYLLABLE: usize = 6;
+
+thread_local! {
+    static SYLLABLE_SET: std::collections::HashSet<&'static str> =
+        SYLLABLES.split_whitespace().collect();
+}
+
+/// split a letter with tone mark into the letter without tone and the tone
+///
+/// `ü` is turned into `v`, and its tone is 0(no tone mark)
+pub fn tone_of(c: char) -> Option<(char, u8)> {
+    #[rustfmt::skip]
+    let tone = match c {
+        'ā' => ('a', 1), 'á' => ('a', 2), 'ǎ' => ('a', 3), 'à' => ('a', 4),
+        'ē' => ('e', 1), 'é' => ('e', 2), 'ě' => ('e', 3), 'è' => ('e', 4),
+        'ī' => ('i', 1), 'í' => ('i', 2), 'ǐ' => ('i', 3), 'ì' => ('i', 4),
+        'ō' => ('o', 1), 'ó' => ('o', 2), 'ǒ' => ('o', 3), 'ò' => ('o', 4),
+        'ū' => ('u', 1), 'ú' => ('u', 2), 'ǔ' => ('u', 3), 'ù' => ('u', 4),
+        'ǖ' => ('v', 1), 'ǘ' => ('v', 2), 'ǚ' => ('v', 3), 'ǜ' => ('v', 4),
+        'ü' => ('v', 0),
+        _ => return None,
+    };
+    Some(tone)
+}
+
+/// return if the word contains any letter with tone mark(or `ü`)
+pub fn has_tone_mark(word: &str) -> bool {
+    word.chars().any(|c| tone_of(c).is_some())
+}
+
+/// split `letters` into syllables, each syllable contains at most one tone mark
+///
+/// the way with fewest syllables is chosen, so `xian` is one syllable but `xīān` is two
+fn segment(letters: &[(char, u8)]) -> Option<String> {
+    // best[i]: the fewest syllables for letters[..i], and where the last syllable starts
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; letters.len() + 1];
+    best[0] = Some((0, 0));
+
+    SYLLABLE_SET.with(|syllables| {
+        for end in 1..=letters.len() {
+            for start in end.saturating_sub(LONGEST_SYLLABLE)..end {
+                let Some((count, _)) = best[start] else {
+                    continue;
+                };
+                let syllable = &letters[start..end];
+                let tones = syllable.iter().filter(|(_, tone)| *tone != 0).count();
+                let string = syllable.iter().map(|(c, _)| *c).collect::<String>();
+                if tones > 1 || !syllables.contains(string.as_str()) {
+                    continue;
+                }
+                if !matches!(best[end], Some((best, _)) if best <= count + 1) {
+                    best[end] = Some((count + 1, start));
+                }
+            }
+        }
+    });
+
+    let mut syllables = vec![];
+    let mut end = letters.len();
+    while end != 0 {
+        let (_, start) = best[end]?;
+        let syllable = &letters[start..end];
+        let mut string = syllable.iter().map(|(c, _)| *c).collect::<String>();
+        if let Some((_, tone)) = syllable.iter().find(|(_, tone)| *tone != 0) {
+            string.push(char::from(b'0' + tone));
+        }
+        syllables.push(string);
+        end = start;
+    }
+    syllables.reverse();
+    Some(syllables.concat())
+}
+
+/// convert tone marks in `word` into numbered pinyin, like `ruò` into `ruo4`, and
+/// `jiégòu` into `jie2gou4`
+///
+/// characters other than letters (like digits and `_`) split the word, and are kept as they
+/// are
+///
+/// return [`None`] if the word cant be split into pinyin syllables
+pub fn numbered(word: &str) -> Option<String> {
+    let mut numbered = String::new();
+    let mut letters = vec![];
+    for c in word.chars() {
+        match tone_of(c) {
+            Some(letter) => letters.push(letter),
+            None if c.is_ascii_alphabetic() => letters.push((c, 0)),
+            None => {
+                numbered += &segment_or_keep(&letters)?;
+                letters.clear();
+                numbered.push(c);
+            }
+        }
+    }
+    numbered += &segment_or_keep(&letters)?;
+    Some(numbered)
+}
+
+/// letters without tone marks are kept, because they may not be pinyin at all
+fn segment_or_keep(letters: &[(char, u8)]) -> Option<String> {
+    if letters.iter().all(|(_, tone)| *tone == 0) && !letters.iter().any(|(c, _)| *c == 'v') {
+        return Some(letters.iter().map(|(c, _)| *c).collect());
+    }
+    segment(letters)
+}
+
+/// remove tone numbers in numbered pinyin, like `jie2gou4` into `jiegou`
+pub fn toneless(word: &str) -> String {
+    word.chars().filter(|c| !c.is_ascii_digit()).collect()
+}
+
+/// map from toneless spellings to numbered keywords, which is used in tone insensitive mode
+#[derive(Debug, Default)]
+pub struct TonelessKeywords {
+    map: HashMap<String, Vec<&'static str>>,
+}
+
+impl TonelessKeywords {
+    pub fn new(keywords: impl IntoIterator<Item = &'static str>) -> Self {
+        let mut map = HashMap::<_, Vec<_>>::new();
+        for keyword in keywords {
+            // only numbered pinyin keywords have tones
+            if !keyword.is_ascii() || !keyword.chars().any(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            let spellings = map.entry(toneless(keyword)).or_default();
+            if !spellings.contains(&keyword) {
+                spellings.push(keyword);
+            }
+        }
+        map.values_mut().for_each(|spellings| spellings.sort());
+        Self { map }
+    }
+
+    /// all keywords which are written as `word` if tones are ignored
+    ///
+    /// return an empty slice if `word` has tone numbers or is not a keyword
+    pub fn get(&self, word: &str) -> &[&'static str] {
+        if word.chars().any(|c| c.is_ascii_digit()) {
+            return &[];
+        }
+        self.map.get(word).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tone_marks() {
+        assert_eq!(numbered("ruò").as_deref(), Some("ruo4"));
+        assert_eq!(numbered("jiégòu").as_deref(), Some("jie2gou4"));
+        assert_eq!(numbered("xiān").as_deref(), Some("xian1"));
+        assert_eq!(numbered("xīān").as_deref(), Some("xi1an1"));
+        assert_eq!(numbered("nǚ").as_deref(), Some("nv3"));
+        assert_eq!(numbered("lüe").as_deref(), Some("lve"));
+        assert_eq!(numbered("fǎn_2").as_deref(), Some("fan3_2"));
+        assert_eq!(numbered("café"), None);
+    }
+
+    #[test]
+    fn toneless_keywords() {
+        let keywords = TonelessKeywords::new(["jie2", "jia1", "jia3", "jie2gou4", "若"]);
+        assert_eq!(keywords.get("jie"), ["jie2"]);
+        assert_eq!(keywords.get("jia"), ["jia1", "jia3"]);
+        assert_eq!(keywords.get("jiegou"), ["jie2gou4"]);
+        assert!(keywords.get("jie2").is_empty());
+        assert!(keywords.get("ruo").is_empty());
+    }
+}
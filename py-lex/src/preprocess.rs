@@ -1,6 +1,6 @@
 crate::front_parse_keywords! {
     keywords Preprocess {
-        "yin3ru4"  -> Include,
-        "ti4huan4" -> Replace,
+        "yin3ru4"  | "引入" -> Include,
+        "ti4huan4" | "替换" -> Replace,
     }
 }
@@ -0,0 +1,101 @@
+//! "did you mean" suggestions for misspelled names and keywords
+
+use crate::pinyin::toneless;
+
+/// the levenshtein distance between two words
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    // distances from a[..i] to every b[..j]
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let replace = previous + (ca != *cb) as usize;
+            previous = row[j + 1];
+            row[j + 1] = replace.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// find the most similar word to `word` in `candidates`
+///
+/// pinyin which only differs in tones (like `ruo3` and `ruo` for `ruo4`) is the most similar,
+/// otherwise the edit distance should be small enough to be a typo
+///
+/// words shorter than 3 characters are only similar to words which differ in tones, because
+/// one typo in them could turn them into almost any other short word
+pub fn similar<'c, I>(word: &str, candidates: I) -> Option<&'c str>
+where
+    I: IntoIterator<Item = &'c str>,
+{
+    let max_distance = match word.chars().count() {
+        ..=2 => 0,
+        len => (len / 3).max(1),
+    };
+    let word_toneless = toneless(word);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != word)
+        .filter_map(|candidate| {
+            if !word_toneless.is_empty() && toneless(candidate) == word_toneless {
+                return Some((0, candidate));
+            }
+            let distance = edit_distance(word, candidate);
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        // candidates with the same distance are sorted by name, to make the result stable
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// the "did you mean" note for `word`, if there is a similar word in `candidates`
+pub fn did_you_mean<'c, I>(word: &str, candidates: I) -> Option<String>
+where
+    I: IntoIterator<Item = &'c str>,
+{
+    similar(word, candidates).map(|similar| format!("did you mean `{similar}`?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("", "jie2"), 4);
+        assert_eq!(edit_distance("jie2", "jie2"), 0);
+        assert_eq!(edit_distance("fan3", "fna3"), 2);
+        assert_eq!(edit_distance("counter", "conter"), 1);
+        assert_eq!(edit_distance("结构", "结"), 1);
+    }
+
+    #[test]
+    fn similar_words() {
+        let keywords = ["ruo4", "ze2", "fan3", "jie2", "jie2gou4"];
+        // wrong or missing tones
+        assert_eq!(similar("ruo3", keywords), Some("ruo4"));
+        assert_eq!(similar("ruo", keywords), Some("ruo4"));
+        assert_eq!(similar("jiegou", keywords), Some("jie2gou4"));
+        // typos
+        assert_eq!(similar("fna3", keywords), None);
+        assert_eq!(similar("fa3", keywords), Some("fan3"));
+        // the word itself is not a suggestion
+        assert_eq!(similar("ze2", keywords), None);
+        assert_eq!(similar("hello", keywords), None);
+        // short words
+        assert_eq!(similar("ze", keywords), Some("ze2"));
+        assert_eq!(similar("z2", keywords), None);
+        assert_eq!(similar("x", ["y", "xs"]), None);
+
+        let names = ["counter", "count", "total"];
+        assert_eq!(similar("conter", names), Some("counter"));
+        assert_eq!(similar("totl", names), Some("total"));
+        assert_eq!(
+            did_you_mean("cont", names).as_deref(),
+            Some("did you mean `count`?")
+        );
+    }
+}
@@ -1,40 +1,48 @@
 crate::reverse_parse_keywords! {
     keywords StructsDefinition {
-        "lei4"     -> Classs,
-        "mei2"     -> Enum,
-        "lian2"    -> Union,
-        "jie2gou4" -> Struct,
+        "lei4"     | "类"   -> Classs,
+        "mei2"     | "枚"   -> Enum,
+        "lian2"    | "联"   -> Union,
+        "jie2gou4" | "结构" -> Struct,
     }
     keywords Symbol {
-        "dao3chu1" -> Export,
+        "dao3chu1" | "导出" -> Export,
 
-        "ya1"      -> FnCallL,
-        "ru4"      -> FnCallR,
+        "ya1"      | "压"   -> FnCallL,
+        "ru4"      | "入"   -> FnCallR,
 
-        "jie2"     -> BracketL,
-        "he2"      -> BracketR,
+        "jie2"     | "结"   -> BracketL,
+        "he2"      | "合"   -> BracketR,
 
-        "zu3"      -> ArrayL,
-        "he2"      -> ArrayR,
+        "zu3"      | "组"   -> ArrayL,
+        "he2"      | "合"   -> ArrayR,
 
-        "han2"     -> Block,
-        "can1"     -> Parameter,
-        "shi4"     -> Comment,
-        "jie2"     -> EndOfBlock,
+        "han2"     | "函"   -> Block,
+        "can1"     | "参"   -> Parameter,
+        "shi4"     | "释"   -> Comment,
+        "zhu4"     | "注"   -> LineComment,
+        "jie2"     | "结"   -> EndOfBlock,
 
-        "fen1"     -> Semicolon,
-        "wei2"     -> Assign,
-        "de1"      -> GetElement,
-        "biao1"    -> Label,
-        "wen2"     -> Char,
-        "chuan4"   -> String,
+        "fen1"     | "分"   -> Semicolon,
+        "wei2"     | "为"   -> Assign,
+        "ling4"    | "令"   -> Let,
+        "de1"      | "的"   -> GetElement,
+        "biao1"    | "标"   -> Label,
+        "wen2"     | "文"   -> Char,
+        "chuan4"   | "串"   -> String,
     }
     keywords ControlFlow {
-        "ruo4"      -> If,
-        "ze2"       -> Else,
-        "chong2"    -> Repeat,
-        "qie4huan4" -> Switch,
-        "tiao4"     -> Jump,
-        "fan3"      -> Return,
+        "ruo4"      | "若"   -> If,
+        "ze2"       | "则"   -> Else,
+        "chong2"    | "重"   -> Repeat,
+        "xun2huan2" | "循环" -> For,
+        "qie4huan4" | "切换" -> Switch,
+        "tiao4"     | "跳"   -> Jump,
+        "fan3"      | "返"   -> Return,
+    }
+    keywords Literals {
+        "zhen1"     | "真"   -> True,
+        "jia3"      | "假"   -> False,
+        "kong1zhi3" | "空指" -> Null,
     }
 }
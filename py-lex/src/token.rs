@@ -4,6 +4,13 @@ pub struct Token {
     pub string: String,
     /// note: span here are span in [`Buffer<char>`]
     span: Span,
+    /// whitespaces and comments before the token
+    pub leading_trivia: Vec<Trivia>,
+    /// whitespaces and comments after the token, only the last token in the source has them
+    pub trailing_trivia: Vec<Trivia>,
+    /// how the token is written in the source, if it is a keyword written without tones, like
+    /// `ruo` for `ruo4`
+    pub toneless: Option<String>,
 }
 
 impl Token {
@@ -11,6 +18,46 @@ impl Token {
         Self {
             string: string.into(),
             span,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+            toneless: None,
+        }
+    }
+
+    /// the span in chars of a span in `tokens`
+    ///
+    /// spans out of `tokens` are clamped to the last token, like where the parser stops at the
+    /// end of an unfinished file
+    pub fn char_span(tokens: &[Token], span: Span) -> Span {
+        let Some(last) = tokens.len().checked_sub(1) else {
+            return Span::new(0, 0);
+        };
+        let start = tokens[span.start.min(last)].get_span();
+        let end = tokens[span.end.saturating_sub(1).min(last)].get_span();
+        start + end
+    }
+}
+
+/// source code between tokens, which makes no sense for the parser, but is kept so that tools
+/// can write the source back
+///
+/// note: span here are span in [`Buffer<char>`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trivia {
+    /// whitespaces and separators
+    Whitespace(Span),
+    /// `shi4 ... jie2`, which could be nested
+    BlockComment(Span),
+    /// `zhu4 ...` until the end of the line
+    LineComment(Span),
+}
+
+impl WithSpan for Trivia {
+    fn get_span(&self) -> Span {
+        match self {
+            Trivia::Whitespace(span) | Trivia::BlockComment(span) | Trivia::LineComment(span) => {
+                *span
+            }
         }
     }
 }
@@ -36,14 +83,37 @@ impl WithSpan for Token {
     }
 }
 
-impl ParseUnit<char> for Token {
-    type Target = Self;
+/// how [`Tokens`] split the source into [`Token`]s
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LexMode {
+    /// only `[A-Za-z0-9_]` are parts of tokens, other characters are separators
+    #[default]
+    Ascii,
+    /// unicode letters and digits are parts of tokens too, so identifiers and keywords can
+    /// be written in hanzi, like `若` and `结果`
+    Unicode,
+}
 
-    fn parse(p: &mut Parser<char>) -> ParseResult<Self, char> {
-        fn w(c: &char) -> bool {
-            c.is_ascii_alphanumeric() || *c == '_'
-        }
+/// letters with tone marks are parts of tokens too, they will be converted to numbered pinyin
+///
+/// so words with accented letters which are not pinyin, like `café`, are errors unless they
+/// are in literals
+fn is_ascii_token(c: &char) -> bool {
+    c.is_ascii_alphanumeric() || *c == '_' || crate::pinyin::tone_of(*c).is_some()
+}
+
+fn is_unicode_token(c: &char) -> bool {
+    c.is_alphanumeric() || *c == '_'
+}
 
+/// all non-ascii characters are kept in literals, even they are not letters
+fn is_literal_token(c: &char) -> bool {
+    is_ascii_token(c) || !c.is_ascii() && !c.is_whitespace()
+}
+
+impl Token {
+    /// parse a token, which is made up by characters `w` returns true
+    fn parse_with(p: &mut Parser<char>, w: fn(&char) -> bool) -> ParseResult<Self, char> {
         // skip whitespace
         while p.next_if(|c| !w(c)).is_some() {}
 
@@ -59,10 +129,195 @@ impl ParseUnit<char> for Token {
             return p.unmatch("empty string");
         }
 
-        Ok(Token {
-            string,
-            span: p.get_span(),
-        })
+        Ok(Token::new(string, p.get_span()))
+    }
+}
+
+impl ParseUnit<char> for Token {
+    type Target = Self;
+
+    #[inline]
+    fn parse(p: &mut Parser<char>) -> ParseResult<Self, char> {
+        Self::parse_with(p, is_ascii_token)
+    }
+}
+
+/// options for [`Tokens`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LexOptions {
+    pub mode: LexMode,
+    /// keywords can be written without tones, like `ruo` for `ruo4`
+    ///
+    /// its an error if more than one keywords are written in the same way, like `jia` for
+    /// both `jia1` and `jia3`. names of primitive types are counted too, so `fu` is an error
+    /// for `fu2` and `fu4`
+    ///
+    /// identifiers cant be spelled like keywords without tones, like `wei` for `wei2`
+    pub tone_insensitive: bool,
+}
+
+impl From<LexMode> for LexOptions {
+    fn from(mode: LexMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+}
+
+/// all [`Token`]s in the source, with default [`LexOptions`]
+///
+/// the token right after `wen2` or `chuan4` is a literal, so non-ascii characters in it are
+/// kept, like `wen2 中`
+///
+/// tone marks in other tokens are converted to numbered pinyin, like `ruò` to `ruo4`
+///
+/// comments are [`Trivia`] of tokens, so they could be written anywhere between tokens
+///
+/// the word right after `wen2` or `chuan4` in a block comment is kept as it is too, so
+/// `shi4 ends at chuan4 jie2 jie2` is one comment
+#[derive(Debug, Clone, Copy)]
+pub struct Tokens;
+
+// `Symbol::Comment`, `Symbol::EndOfBlock` and `Symbol::LineComment` in all spellings
+const COMMENTS: [&str; 2] = ["shi4", "释"];
+const END_OF_COMMENTS: [&str; 2] = ["jie2", "结"];
+const LINE_COMMENTS: [&str; 2] = ["zhu4", "注"];
+// `Symbol::Char` and `Symbol::String` in all spellings
+const LITERALS: [&str; 4] = ["wen2", "chuan4", "文", "串"];
+
+impl Tokens {
+    /// skip whitespaces and separators
+    fn whitespace(p: &mut Parser<char>, w: fn(&char) -> bool) -> Option<Trivia> {
+        let skipped = p.once(|p| {
+            if p.next_if(|c| !w(c)).is_none() {
+                return p.unmatch("no whitespace");
+            }
+            while p.next_if(|c| !w(c)).is_some() {}
+            Ok(p.get_span())
+        });
+        skipped.ok().map(Trivia::Whitespace)
+    }
+
+    /// skip the rest of a comment after `opener`, nested comments are skipped too
+    fn comment(p: &mut Parser<char>, opener: &Token) -> Result<Trivia, ParseError> {
+        if LINE_COMMENTS.contains(&&**opener) {
+            let mut end = opener.span.end;
+            while p.next_if(|c| *c != '\n').is_some() {
+                end += 1;
+            }
+            return Ok(Trivia::LineComment(Span::new(opener.span.start, end)));
+        }
+
+        let mut depth = 1;
+        let mut literal = false;
+        loop {
+            // everything could be written in comments
+            let Some(word) = p
+                .once(|p| Token::parse_with(p, is_literal_token))
+                .apply(mapper::Try)?
+            else {
+                return opener.throw("comment without ending");
+            };
+            if std::mem::replace(&mut literal, LITERALS.contains(&&*word)) {
+                // escaped by `wen2` or `chuan4`
+            } else if COMMENTS.contains(&&*word) {
+                depth += 1;
+            } else if END_OF_COMMENTS.contains(&&*word) {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(Trivia::BlockComment(opener.span.merge(word.span)));
+                }
+            }
+        }
+    }
+
+    /// write the source code back from `tokens` and their [`Trivia`]
+    pub fn restore(chars: &[char], tokens: &[Token]) -> String {
+        let mut src = String::new();
+        let mut write = |span: Span| src.extend(&chars[span.start..span.end]);
+        for token in tokens {
+            token
+                .leading_trivia
+                .iter()
+                .for_each(|trivia| write(trivia.get_span()));
+            write(token.span);
+            token
+                .trailing_trivia
+                .iter()
+                .for_each(|trivia| write(trivia.get_span()));
+        }
+        src
+    }
+
+    pub fn parse_with(p: &mut Parser<char>, options: LexOptions) -> Result<Vec<Token>, ParseError> {
+        let w = match options.mode {
+            LexMode::Ascii => is_ascii_token,
+            LexMode::Unicode => is_unicode_token,
+        };
+        let toneless_keywords = match options.tone_insensitive {
+            true => {
+                let keywords = crate::keywords();
+                #[cfg(feature = "types")]
+                let keywords = keywords.into_iter().chain(crate::types::PRIMITIVE_TYPES);
+                crate::pinyin::TonelessKeywords::new(keywords)
+            }
+            false => Default::default(),
+        };
+
+        let mut tokens = Vec::<Token>::new();
+        let mut trivia = vec![];
+        loop {
+            let literal = tokens
+                .last()
+                .is_some_and(|last| LITERALS.contains(&&**last));
+            let w = if literal { is_literal_token } else { w };
+            trivia.extend(Self::whitespace(p, w));
+            let Some(mut token) = p.once(|p| Token::parse_with(p, w)).apply(mapper::Try)? else {
+                if let Some(last) = tokens.last_mut() {
+                    last.trailing_trivia = trivia;
+                }
+                return Ok(tokens);
+            };
+
+            if !literal && crate::pinyin::has_tone_mark(&token) {
+                match crate::pinyin::numbered(&token) {
+                    Some(numbered) => token.string = numbered,
+                    None => return token.throw(format!("`{token}` is not a valid pinyin")),
+                }
+            }
+            if !literal {
+                match toneless_keywords.get(&token) {
+                    [] => {}
+                    [keyword] => {
+                        let toneless = std::mem::replace(&mut token.string, keyword.to_string());
+                        token.toneless = Some(toneless);
+                    }
+                    keywords => {
+                        let keywords = keywords.join("`, `");
+                        let msg = format!(
+                            "`{token}` is ambiguous, it could be any of `{keywords}`, write the tone to tell them apart"
+                        );
+                        return token.throw(msg);
+                    }
+                }
+            }
+            if !literal && (COMMENTS.contains(&&*token) || LINE_COMMENTS.contains(&&*token)) {
+                trivia.push(Self::comment(p, &token)?);
+                continue;
+            }
+            token.leading_trivia = std::mem::take(&mut trivia);
+            tokens.push(token);
+        }
+    }
+}
+
+impl ParseUnit<char> for Tokens {
+    type Target = Vec<Token>;
+
+    #[inline]
+    fn parse(p: &mut Parser<char>) -> ParseResult<Self, char> {
+        Self::parse_with(p, LexOptions::default())
     }
 }
 
@@ -92,8 +347,7 @@ impl Source for Token {
         S: std::fmt::Write,
     {
         let (chars, tokens) = with;
-        let loc = tokens[loc.start].get_span() + tokens[loc.end - 1].get_span();
-        char::handle_location(chars, buffer, loc, msg)
+        char::handle_location(chars, buffer, Token::char_span(tokens, loc), msg)
     }
 }
 
@@ -245,4 +499,183 @@ mod tests {
             assert_eq!(got, expect);
         }
     }
+
+    fn tokens(src: &str, options: impl Into<LexOptions>) -> Vec<String> {
+        let buffer = Buffer::new("test".to_owned(), src.chars().collect());
+        let mut parser = Parser::new(buffer);
+        let tokens = Tokens::parse_with(&mut parser, options.into()).unwrap();
+        tokens.into_iter().map(|token| token.string).collect()
+    }
+
+    #[test]
+    fn unicode_tokens() {
+        let src = "若 结果，大 1 wen2 中 chuan4 你好！";
+        assert_eq!(
+            tokens(src, LexMode::Ascii),
+            ["1", "wen2", "中", "chuan4", "你好！"]
+        );
+        assert_eq!(
+            tokens(src, LexMode::Unicode),
+            ["若", "结果", "大", "1", "wen2", "中", "chuan4", "你好！"]
+        );
+    }
+
+    #[test]
+    fn tone_marks() {
+        let src = "ruò can1 jiégòu jie2 chuan4 ruò";
+        assert_eq!(
+            tokens(src, LexMode::Ascii),
+            ["ruo4", "can1", "jie2gou4", "jie2", "chuan4", "ruò"]
+        );
+    }
+
+    #[test]
+    fn tone_insensitive() {
+        let options = LexOptions {
+            tone_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tokens("ruo can jie2 x wei 1 fen", options),
+            ["ruo4", "can1", "jie2", "x", "wei2", "1", "fen1"]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn tone_insensitive_ambiguous() {
+        let options = LexOptions {
+            tone_insensitive: true,
+            ..Default::default()
+        };
+        // `jia1` and `jia3`
+        tokens("fan3 x jia 1 fen1", options);
+    }
+
+    #[test]
+    fn tone_insensitive_types() {
+        let options = LexOptions {
+            tone_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tokens("zheng x wei 1", options),
+            ["zheng3", "x", "wei2", "1"]
+        );
+
+        // `fu2` and `fu4`
+        let buffer = Buffer::new("test".to_owned(), "fan3 fu 1 fen1".chars().collect());
+        let mut parser = Parser::new(buffer);
+        assert!(Tokens::parse_with(&mut parser, options).is_err());
+    }
+
+    #[test]
+    fn toneless_spellings() {
+        let options = LexOptions {
+            tone_insensitive: true,
+            ..Default::default()
+        };
+        let buffer = Buffer::new("test".to_owned(), "he wei2 x".chars().collect());
+        let mut parser = Parser::new(buffer);
+        let tokens = Tokens::parse_with(&mut parser, options).unwrap();
+        assert_eq!(tokens[0].string, "he2");
+        assert_eq!(tokens[0].toneless.as_deref(), Some("he"));
+        assert_eq!(tokens[1].toneless, None);
+        assert_eq!(tokens[2].toneless, None);
+    }
+
+    #[test]
+    fn accented_words() {
+        // words with tone marks which are not pinyin are errors out of literals
+        let buffer = Buffer::new("test".to_owned(), "ling4 café wei2 1".chars().collect());
+        let mut parser = Parser::new(buffer);
+        assert!(Tokens::parse_with(&mut parser, LexMode::Ascii.into()).is_err());
+
+        assert_eq!(tokens("chuan4 café", LexMode::Ascii), ["chuan4", "café"]);
+    }
+
+    #[test]
+    fn comments() {
+        let src = "han2shu4 shi4 a function jie2 f can1 zheng3 shi4 x jie2 a jie2 fen1";
+        assert_eq!(
+            tokens(src, LexMode::Ascii),
+            ["han2shu4", "f", "can1", "zheng3", "a", "jie2", "fen1"]
+        );
+        // nested comments
+        let src = "1 shi4 outer shi4 inner jie2 still outer jie2 jia1 2";
+        assert_eq!(tokens(src, LexMode::Ascii), ["1", "jia1", "2"]);
+        // line comments
+        let src = "1 zhu4 until the end of line jie2 \n jia1 注 注释 结 \n 2";
+        assert_eq!(tokens(src, LexMode::Unicode), ["1", "jia1", "2"]);
+        // comment keywords in literals are not comments
+        assert_eq!(tokens("chuan4 shi4", LexMode::Ascii), ["chuan4", "shi4"]);
+    }
+
+    #[test]
+    fn comment() {
+        // in item positions
+        let src = "shi4 ehhhaaaaaaaaaaaaaaaaaaaaaaaa jie2 zheng3 f can1 jie2 han2 jie2";
+        assert_eq!(
+            tokens(src, LexMode::Ascii),
+            ["zheng3", "f", "can1", "jie2", "han2", "jie2"]
+        );
+        // in statement positions
+        let src = "han2 shi4 ehhhaaaaaaaaaaaaaaaaaaaaaaaa jie2 fan3 1 fen1 jie2";
+        assert_eq!(
+            tokens(src, LexMode::Ascii),
+            ["han2", "fan3", "1", "fen1", "jie2"]
+        );
+    }
+
+    #[test]
+    fn escaped_end_of_comment() {
+        let src = "han2 shi4 loop ends at chuan4 jie2 here jie2 fan3 1 fen1 jie2";
+        assert_eq!(
+            tokens(src, LexMode::Ascii),
+            ["han2", "fan3", "1", "fen1", "jie2"]
+        );
+        let src = "1 shi4 wen2 shi4 opens nothing jie2 jia1 2";
+        assert_eq!(tokens(src, LexMode::Ascii), ["1", "jia1", "2"]);
+        // a `jie2` without `chuan4` ends the comment
+        let src = "1 shi4 loop ends at jie2 here jie2";
+        assert_eq!(tokens(src, LexMode::Ascii), ["1", "here", "jie2"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn comment_without_ending() {
+        tokens("1 shi4 outer shi4 inner jie2", LexMode::Ascii);
+    }
+
+    #[test]
+    #[should_panic]
+    fn comment_without_ending_in_items() {
+        tokens("zheng3 f can1 jie2 han2 jie2 shi4 ehhh", LexMode::Ascii);
+    }
+
+    #[test]
+    #[should_panic]
+    fn comment_without_ending_in_statements() {
+        tokens("han2 shi4 ends at chuan4 jie2", LexMode::Ascii);
+    }
+
+    #[test]
+    fn restore() {
+        let src = "  shi4 leading jie2 fan3 shi4 a shi4 b jie2 jie2 x fen1\n\tzhu4 line\n  ";
+        let chars = src.chars().collect::<Vec<_>>();
+        let buffer = Buffer::new("test".to_owned(), chars.clone());
+        let mut parser = Parser::new(buffer);
+        let tokens = Tokens::parse_with(&mut parser, LexMode::Ascii.into()).unwrap();
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0].leading_trivia.len(), 3);
+        assert!(matches!(
+            tokens[2].trailing_trivia.as_slice(),
+            [
+                Trivia::Whitespace(_),
+                Trivia::LineComment(_),
+                Trivia::Whitespace(_)
+            ]
+        ));
+        assert_eq!(Tokens::restore(&chars, &tokens), src);
+    }
 }
@@ -1,3 +1,7 @@
+/// names of primitive types, they are identifiers rather than keeping keywords, but they are
+/// spelled like keywords too
+pub const PRIMITIVE_TYPES: [&str; 6] = ["zheng3", "fu2", "bu4", "整", "浮", "布"];
+
 crate::front_parse_keywords! {
     // keywords PrimitiveType {
     //     "zheng3" -> Integer,
@@ -7,12 +11,12 @@ crate::front_parse_keywords! {
     //     "xu1"    -> Complex,
     // }
     keywords BasicExtenWord {
-        "zu3"      -> Array,
-        "kuan1"    -> Width,
-        "you3fu2"  -> Signed,
-        "wu2fu2"   -> Unsigned,
-        "yin3"     -> Reference,
-        "she4"     -> Const,
-        "zhi3"     -> Pointer,
+        "zu3"      | "组"   -> Array,
+        "kuan1"    | "宽"   -> Width,
+        "you3fu2"  | "有符" -> Signed,
+        "wu2fu2"   | "无符" -> Unsigned,
+        "yin3"     | "引"   -> Reference,
+        "she4"     | "设"   -> Const,
+        "zhi3"     | "指"   -> Pointer,
     }
 }
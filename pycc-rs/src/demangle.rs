@@ -0,0 +1,131 @@
+use py_ast::semantic::mangle::{AsciiMangler, ChineseMangler, Mangle, MangleItem, MangleUnit};
+use std::io::BufRead;
+
+/// demangle a function symbol from any mangler
+///
+/// names of functions wrapped by the c backend (`_` followed by base32) are also supported
+pub fn demangle(symbol: &str) -> Option<MangleUnit<'static>> {
+    fn demangle_fn<M: Mangle>(symbol: &str) -> Option<MangleUnit<'static>> {
+        // any word could be demangled as a type, but only functions are symbols
+        M::demangle(symbol).filter(|unit| matches!(unit.item, MangleItem::Fn { .. }))
+    }
+
+    let demangled =
+        demangle_fn::<ChineseMangler>(symbol).or_else(|| demangle_fn::<AsciiMangler>(symbol));
+    #[cfg(feature = "backend-c")]
+    let demangled = demangled.or_else(|| {
+        let symbol = py_codegen_c::decode_base32(symbol.strip_prefix('_')?)?;
+        demangle_fn::<ChineseMangler>(&symbol).or_else(|| demangle_fn::<AsciiMangler>(&symbol))
+    });
+    demangled
+}
+
+/// replace all symbols in the line with their readable forms, like `c++filt`
+///
+/// symbols are made of words separated by single spaces, so names mangled by
+/// [`ChineseMangler`] can be found too
+pub fn demangle_line(line: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    // (start, end) of words
+    let mut words = vec![];
+    let mut start = None;
+    for (idx, c) in line.char_indices() {
+        match (is_word(c), start) {
+            (true, None) => start = Some(idx),
+            (false, Some(s)) => {
+                words.push((s, idx));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, line.len()));
+    }
+
+    let mut output = String::new();
+    let mut written = 0;
+    let mut nth = 0;
+    while nth < words.len() {
+        let start = words[nth].0;
+        let mut end = nth;
+        let demangled = loop {
+            if let Some(unit) = demangle(&line[start..words[end].1]) {
+                break Some(unit);
+            }
+            match words.get(end + 1) {
+                Some(next) if &line[words[end].1..next.0] == " " => end += 1,
+                _ => break None,
+            }
+        };
+        match demangled {
+            Some(unit) => {
+                output += &line[written..start];
+                output += &unit.to_string();
+                written = words[end].1;
+                nth = end + 1;
+            }
+            None => nth += 1,
+        }
+    }
+    output + &line[written..]
+}
+
+/// demangle symbols in `symbols`, or every line read from stdin if there are no symbols
+pub fn run(symbols: Vec<String>) -> std::io::Result<()> {
+    if !symbols.is_empty() {
+        for symbol in symbols {
+            match demangle(&symbol) {
+                Some(unit) => println!("{unit}"),
+                None => println!("{symbol}"),
+            }
+        }
+        return Ok(());
+    }
+
+    for line in std::io::stdin().lock().lines() {
+        println!("{}", demangle_line(&line?));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbols() {
+        assert_eq!(demangle("jia 参 i64 结").unwrap().to_string(), "jia(i64)");
+        assert_eq!(demangle("_Z3jiax").unwrap().to_string(), "jia(i64)");
+        assert!(demangle("jia").is_none());
+        assert!(demangle("i64").is_none());
+    }
+
+    #[cfg(feature = "backend-c")]
+    #[test]
+    fn base32() {
+        for name in ["jia 参 i64 结", "_Zu12_52a0__4e00_x"] {
+            let symbol = format!("_{}", py_codegen_c::encode_base32(name));
+            assert_eq!(demangle(&symbol), demangle(name));
+        }
+        assert!(demangle("_main").is_none());
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(
+            demangle_line("define i64 @\"jia 参 i64 指 u8 结\"(i64 %0, ptr %1) {"),
+            "define i64 @\"jia(i64, * u8)\"(i64 %0, ptr %1) {"
+        );
+        assert_eq!(
+            demangle_line("0000000000001139 T _Z3jiax"),
+            "0000000000001139 T jia(i64)"
+        );
+        assert_eq!(
+            demangle_line("call _Z3jiax and jian 参 i64 结 then ret"),
+            "call jia(i64) and jian(i64) then ret"
+        );
+        assert_eq!(demangle_line("nothing to see here"), "nothing to see here");
+    }
+}
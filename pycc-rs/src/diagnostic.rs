@@ -0,0 +1,188 @@
+use clap::ValueEnum;
+use py_lex::Token;
+use serde_json::{json, Value};
+use terl::{Buffer, Error, Message, Severity, Source, Span};
+
+/// how errors and warnings are printed
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// readable text with the source code marked
+    Human,
+    /// a json object in a line for each error or warning, for editors and other tools
+    Json,
+}
+
+/// sources whose spans could be mapped back to chars in the source file
+pub trait Locate: Source {
+    fn chars<'a>(with: &'a Self::HandleErrorWith<'_>) -> &'a Buffer<char>;
+
+    /// the span in chars of a span in this source
+    fn char_span(with: &Self::HandleErrorWith<'_>, span: Span) -> Span;
+}
+
+impl Locate for char {
+    fn chars(with: &Buffer<char>) -> &Buffer<char> {
+        with
+    }
+
+    fn char_span(_: &Buffer<char>, span: Span) -> Span {
+        span
+    }
+}
+
+impl Locate for Token {
+    fn chars<'a>(with: &'a (&Buffer<char>, &Buffer<Token>)) -> &'a Buffer<char> {
+        with.0
+    }
+
+    fn char_span(with: &(&Buffer<char>, &Buffer<Token>), span: Span) -> Span {
+        Token::char_span(with.1, span)
+    }
+}
+
+/// print an error or a warning to stderr in the format
+pub fn emit<S: Locate>(format: ErrorFormat, with: &S::HandleErrorWith<'_>, error: Error) {
+    match format {
+        ErrorFormat::Human => eprintln!("{}", S::handle_error(with, error)),
+        ErrorFormat::Json => eprintln!("{}", to_json::<S>(with, error)),
+    }
+}
+
+/// the first message is the reason of the error, and where it is the primary span. locations in
+/// other messages are secondary spans, and texts in them are notes
+///
+/// the error formatted as [`ErrorFormat::Human`] is also included as `rendered`
+pub fn to_json<S: Locate>(with: &S::HandleErrorWith<'_>, error: Error) -> Value {
+    let chars = S::chars(with);
+    let span = |span: Span, is_primary: bool, label: Option<&str>| {
+        let mut json = location(chars, S::char_span(with, span));
+        json["is_primary"] = is_primary.into();
+        json["label"] = label.into();
+        json
+    };
+
+    let mut messages = error.messages().iter();
+    let (message, mut spans) = match messages.next() {
+        Some(Message::Rich(reason, at)) => (reason.as_str(), vec![span(*at, true, None)]),
+        Some(Message::Location(at)) => ("", vec![span(*at, true, None)]),
+        Some(Message::Text(reason)) => (reason.as_str(), vec![]),
+        None => ("", vec![]),
+    };
+    let mut notes = vec![];
+    for message in messages {
+        match message {
+            Message::Rich(label, at) => spans.push(span(*at, false, Some(label))),
+            Message::Location(at) => spans.push(span(*at, false, None)),
+            Message::Text(note) => notes.push(note.as_str()),
+        }
+    }
+
+    let severity = match error.severity() {
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    };
+    json!({
+        "severity": severity,
+        "message": message,
+        "spans": spans,
+        "notes": notes,
+        "rendered": S::handle_error(with, error.clone()),
+    })
+}
+
+/// byte offsets are counted in utf-8, lines and columns are counted from 1, and columns are
+/// counted in chars
+fn location(chars: &Buffer<char>, span: Span) -> Value {
+    let start = span.start.min(chars.len());
+    let end = span.end.clamp(start, chars.len());
+
+    let byte = |idx: usize| chars[..idx].iter().map(|c| c.len_utf8()).sum::<usize>();
+    let line_column = |idx: usize| {
+        let line = chars[..idx].iter().filter(|c| **c == '\n').count() + 1;
+        let line_start = chars[..idx]
+            .iter()
+            .rposition(|c| *c == '\n')
+            .map_or(0, |pos| pos + 1);
+        (line, idx - line_start + 1)
+    };
+    let (line_start, column_start) = line_column(start);
+    let (line_end, column_end) = line_column(end);
+
+    json!({
+        "file": chars.name(),
+        "byte_start": byte(start),
+        "byte_end": byte(end),
+        "line_start": line_start,
+        "column_start": column_start,
+        "line_end": line_end,
+        "column_end": column_end,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use terl::WithSpan;
+
+    #[test]
+    fn locations() {
+        let chars = Buffer::new("test.py1".to_owned(), "整 x\nab c".chars().collect());
+        let json = location(&chars, Span::new(2, 6));
+        assert_eq!(json["byte_start"], 4);
+        assert_eq!(json["byte_end"], 8);
+        assert_eq!(
+            (&json["line_start"], &json["column_start"]),
+            (&1.into(), &3.into())
+        );
+        assert_eq!(
+            (&json["line_end"], &json["column_end"]),
+            (&2.into(), &3.into())
+        );
+
+        // spans out of the source are clamped
+        let json = location(&chars, Span::new(6, 100));
+        assert_eq!(json["byte_end"], 10);
+    }
+
+    #[test]
+    fn errors() {
+        terl::set_colored(false);
+        let chars = Buffer::new("test.py1".to_owned(), "zheng3 x\nfan3 x".chars().collect());
+        let error = Span::new(14, 15).make_error("unknown variable `x`")
+            + Message::rich("defined here".to_owned(), Span::new(7, 8))
+            + "note: it is out of scope";
+        let json = to_json::<char>(&chars, error.with_severity(Severity::Warning));
+
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["message"], "unknown variable `x`");
+        assert_eq!(json["spans"][0]["is_primary"], true);
+        assert_eq!(json["spans"][0]["line_start"], 2);
+        assert_eq!(json["spans"][1]["label"], "defined here");
+        assert_eq!(json["spans"][1]["byte_start"], 7);
+        assert_eq!(json["notes"][0], "note: it is out of scope");
+        let rendered = json["rendered"].as_str().unwrap();
+        assert!(rendered.starts_with("warning: [test.py1:2:6]: unknown variable `x`"));
+    }
+
+    #[test]
+    fn unfinished_file() {
+        terl::set_colored(false);
+        let src = "zheng3 f can1 jie2 han2 fan3 1 fen1";
+        let source = Buffer::new("test.py1".to_owned(), src.chars().collect());
+        let (chars, mut parser) = terl::Parser::<char>::new(source)
+            .process(|p| py_lex::Tokens::parse_with(p, Default::default()))
+            .unwrap();
+        let _ = parser.parse::<py_ast::parse::Items>();
+        let errors = parser.take_reported();
+        assert!(!errors.is_empty());
+
+        // errors at the end of the file are located at the last token
+        let with = (&chars, parser.buffer());
+        for error in errors.into_iter().map(|error| error.error()) {
+            let rendered = Token::handle_error(&with, error.clone());
+            assert!(rendered.contains("fen1"), "{rendered}");
+            let json = to_json::<Token>(&with, error);
+            assert_eq!(json["spans"][0]["line_start"], 1);
+        }
+    }
+}
@@ -2,12 +2,21 @@
 
 use std::{error::Error, path::PathBuf, process::exit};
 
-use clap::{Parser, ValueEnum};
-use py_ast::semantic::Generate;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use py_ast::semantic::{
+    lint::{Level, Lint, Lints, Warnings},
+    mangle::{AsciiMangler, ChineseMangler, Mangle},
+    Generate,
+};
 use py_codegen::Backend;
 use py_ir::Item;
-use py_lex::Token;
-use terl::{Buffer, ResultMapperExt, Source};
+use py_lex::{LexMode, LexOptions, Token, Tokens};
+use terl::{Buffer, Severity};
+
+mod demangle;
+mod diagnostic;
+
+use diagnostic::ErrorFormat;
 
 #[cfg(all(
     test,
@@ -36,6 +45,59 @@ enum LLVMOutputMode {
     Bitcode,
 }
 
+#[derive(ValueEnum, Clone, Copy)]
+enum SymbolMangler {
+    /// readable names, like `jia 参 i64 结`
+    Chinese,
+    /// ascii, linker-safe names in the style of the Itanium C++ ABI, like `_Z3jiax`
+    Ascii,
+}
+
+/// lints selected by a name in `-A`, `-W` or `-D`
+#[derive(Clone)]
+struct LintSelector(Vec<Lint>);
+
+/// `warnings` selects all lints
+fn parse_lints(name: &str) -> Result<LintSelector, String> {
+    if name == "warnings" {
+        return Ok(LintSelector(Lint::ALL.to_vec()));
+    }
+    Lint::from_name(name)
+        .map(|lint| LintSelector(vec![lint]))
+        .ok_or_else(|| {
+            let names = Lint::ALL.map(|lint| lint.name()).join(", ");
+            format!("unknown lint `{name}`, expect `warnings` or one of: {names}")
+        })
+}
+
+/// levels set by `-A`, `-W` and `-D`, later flags override earlier ones
+fn lint_levels(matches: &ArgMatches) -> Lints {
+    let mut flags = vec![];
+    for (id, level) in [
+        ("allow", Level::Allow),
+        ("warn", Level::Warn),
+        ("deny", Level::Deny),
+    ] {
+        let (Some(indices), Some(selectors)) =
+            (matches.indices_of(id), matches.get_many::<LintSelector>(id))
+        else {
+            continue;
+        };
+        flags.extend(
+            indices
+                .zip(selectors)
+                .map(|(idx, lints)| (idx, level, lints)),
+        );
+    }
+    flags.sort_by_key(|(idx, ..)| *idx);
+
+    let mut lints = Lints::default();
+    for (_, level, selector) in flags {
+        selector.0.iter().for_each(|lint| lints.set(*lint, level));
+    }
+    lints
+}
+
 // #[cfg(feature = "backend-llvm")]
 // #[derive(ValueEnum, Clone, Copy)]
 // enum LLVMOptimizeLevel {
@@ -45,16 +107,78 @@ enum LLVMOutputMode {
 //     O3,
 // }
 
+#[derive(Subcommand)]
+enum Command {
+    /// demangle symbols, or symbols in lines read from stdin if no symbol is given
+    Demangle { symbols: Vec<String> },
+}
+
 #[derive(Parser)]
-#[command(version, about)]
+#[command(
+    version,
+    about,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Cli {
-    src: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(required = true)]
+    src: Option<PathBuf>,
     #[arg(short, long, help = "path for output file, default to be a.out")]
     output: Option<PathBuf>,
     #[arg(long, help = "path for ast output file")]
     output_ast: Option<PathBuf>,
     #[arg(long, help = "path for py-ir output file")]
     output_ir: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "allow unicode letters(like hanzi) in identifiers and keywords"
+    )]
+    unicode: bool,
+    #[arg(
+        long,
+        help = "allow keywords written without tones, like `ruo` for `ruo4`"
+    )]
+    tone_insensitive: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = SymbolMangler::Chinese,
+        help = "how symbol names are mangled"
+    )]
+    mangler: SymbolMangler,
+    #[arg(
+        short = 'A',
+        long,
+        value_name = "LINT",
+        value_parser = parse_lints,
+        help = "allow a lint, or all lints with `warnings`"
+    )]
+    allow: Vec<LintSelector>,
+    #[arg(
+        short = 'W',
+        long,
+        value_name = "LINT",
+        value_parser = parse_lints,
+        help = "report a lint as a warning"
+    )]
+    warn: Vec<LintSelector>,
+    #[arg(
+        short = 'D',
+        long,
+        value_name = "LINT",
+        value_parser = parse_lints,
+        help = "report a lint as an error"
+    )]
+    deny: Vec<LintSelector>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ErrorFormat::Human,
+        help = "how errors and warnings are printed"
+    )]
+    error_format: ErrorFormat,
     #[cfg(any(feature = "backend-llvm-dynamic", feature = "backend-llvm-static"))]
     #[arg(short = 'm', long, value_enum, default_value_t = LLVMOutputMode::Bitcode, help = "llvm ir output mode",)]
     output_mode: LLVMOutputMode,
@@ -78,13 +202,36 @@ struct Cli {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+
+    if let Some(Command::Demangle { symbols }) = cli.command {
+        demangle::run(symbols)?;
+        return Ok(());
+    }
 
     // generate ast
-    let path = &cli.src;
+    let path = cli
+        .src
+        .as_ref()
+        .expect("src is required without subcommands");
     let src = std::fs::read_to_string(path)?;
     let path = path.to_string_lossy().to_string();
-    let (error_handler, ast) = generate_ast(path.clone(), src);
+    let mode = if cli.unicode {
+        LexMode::Unicode
+    } else {
+        LexMode::Ascii
+    };
+    let options = LexOptions {
+        mode,
+        tone_insensitive: cli.tone_insensitive,
+    };
+    let format = cli.error_format;
+    if format == ErrorFormat::Json {
+        // rendered texts in json are not colored
+        terl::set_colored(false);
+    }
+    let (error_handler, ast, parsed) = generate_ast(path.clone(), src, options, format);
     let error_handler = (&error_handler.0, &error_handler.1);
 
     if let Some(ast_path) = cli.output_ast {
@@ -92,7 +239,15 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
 
     // generate ir
-    let ir = generate_ir(error_handler, &ast);
+    let lints = lint_levels(&matches);
+    let ir = match cli.mangler {
+        SymbolMangler::Chinese => {
+            generate_ir::<ChineseMangler>(error_handler, &ast, parsed, lints, format)
+        }
+        SymbolMangler::Ascii => {
+            generate_ir::<AsciiMangler>(error_handler, &ast, parsed, lints, format)
+        }
+    };
     if let Some(ast_path) = cli.output_ir {
         let mut file = std::fs::File::create(ast_path)?;
         serde_json::to_writer(&mut file, &ir)?;
@@ -140,62 +295,73 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn generate_ir(error_handler: (&Buffer, &Buffer<Token>), ast: &[py_ast::parse::Item]) -> Vec<Item> {
-    let mut scope: py_ast::semantic::Defines = Default::default();
+/// semantic errors in intact functions are reported even if there are syntax errors
+fn generate_ir<M: Mangle>(
+    error_handler: (&Buffer, &Buffer<Token>),
+    ast: &[py_ast::parse::Item],
+    parsed: bool,
+    lints: Lints,
+    format: ErrorFormat,
+) -> Vec<Item> {
+    let mut scope = py_ast::semantic::Defines::<M> {
+        warnings: Warnings::new(lints),
+        ..Default::default()
+    };
 
-    match scope.generate(ast) {
-        Ok(mir) => return mir,
+    let result = scope.generate(ast);
+    // warnings of denied lints are errors
+    let mut denied = false;
+    for warning in scope.warnings.take() {
+        denied |= warning.severity() == Severity::Error;
+        diagnostic::emit::<Token>(format, &error_handler, warning);
+    }
+    match result {
+        Ok(mir) if parsed && !denied => return mir,
+        Ok(..) => {}
         Err(err) => match err {
             either::Either::Left(errs) => errs
                 .into_iter()
-                .for_each(|e| eprintln!("{}", py_lex::Token::handle_error(&error_handler, e))),
+                .for_each(|e| diagnostic::emit::<Token>(format, &error_handler, e)),
             either::Either::Right(errss) => errss
                 .into_iter()
                 .flatten()
-                .for_each(|e| eprintln!("{}", py_lex::Token::handle_error(&error_handler, e))),
+                .for_each(|e| diagnostic::emit::<Token>(format, &error_handler, e)),
         },
     }
     exit(-1);
 }
 
-type GenAstResult = ((Buffer, Buffer<Token>), Vec<py_ast::parse::Item>);
+/// buffers, items, and whether there is no syntax error
+type GenAstResult = ((Buffer, Buffer<Token>), Vec<py_ast::parse::Item>, bool);
 
-fn generate_ast(path: String, src: String) -> GenAstResult {
-    let source = Buffer::new(path, src.chars().collect());
+fn generate_ast(
+    path: String,
+    src: String,
+    options: LexOptions,
+    format: ErrorFormat,
+) -> GenAstResult {
+    let source = Buffer::new(path.clone(), src.chars().collect());
     let parser = terl::Parser::<char>::new(source);
-    let (char_buffer, mut parser) = parser
-        .process(|p| {
-            let mut tokens = vec![];
-
-            while let Some(token) = p.parse::<Token>().apply(terl::mapper::Try)? {
-                tokens.push(token);
-            }
-            Ok(tokens)
-        })
-        .unwrap_or_else(|_| unreachable!());
-    let parse_result = (|| -> terl::Result<_, terl::ParseError> {
-        let mut ast = vec![];
-        while parser.peek().is_some() {
-            ast.push(
-                parser
-                    .parse::<py_ast::parse::Item>()
-                    .apply(terl::mapper::MustMatch)?,
-            )
-        }
-        Ok(ast)
-    })();
-    let error_handler = (&char_buffer, parser.buffer());
-    let ast = match parse_result {
-        Ok(ast) => ast,
+    let (char_buffer, mut parser) = match parser.process(|p| Tokens::parse_with(p, options)) {
+        Ok(processed) => processed,
         Err(error) => {
-            eprintln!("{}", parser.calling_tree());
-            eprintln!(
-                "{}",
-                py_lex::Token::handle_error(&error_handler, error.error())
-            );
-
+            // the buffer is moved into the parser, so make a new one
+            let source = Buffer::new(path, src.chars().collect());
+            diagnostic::emit::<char>(format, &source, error.error());
             exit(-1);
         }
     };
-    ((char_buffer, parser.take_buffer()), ast)
+    // errors are reported to the parser, so parsing items never fails
+    let ast = parser.parse::<py_ast::parse::Items>().unwrap_or_default();
+    let errors = parser.take_reported();
+    let parsed = errors.is_empty();
+    // the calling tree is only for humans
+    if !parsed && format == ErrorFormat::Human {
+        eprintln!("{}", parser.calling_tree());
+    }
+    let error_handler = (&char_buffer, parser.buffer());
+    for error in errors {
+        diagnostic::emit::<Token>(format, &error_handler, error.error());
+    }
+    ((char_buffer, parser.take_buffer()), ast, parsed)
 }
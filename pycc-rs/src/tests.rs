@@ -1,3 +1,4 @@
+use py_ast::semantic::mangle::{AsciiMangler, DefaultMangler, Mangle};
 use py_codegen::Backend;
 use py_codegen_llvm::{
     inkwell::{
@@ -7,15 +8,26 @@ use py_codegen_llvm::{
     LLVMBackend,
 };
 use py_ir as ir;
+use py_lex::LexMode;
 
-fn test_generate_ir(src: &str) -> Vec<ir::Item> {
-    let (error_handler, ast) = crate::generate_ast("compiler-test.py1".to_owned(), src.to_owned());
+fn test_generate_ir<M: Mangle>(src: &str, mode: LexMode) -> Vec<ir::Item> {
+    let format = crate::ErrorFormat::Human;
+    let (error_handler, ast, parsed) = crate::generate_ast(
+        "compiler-test.py1".to_owned(),
+        src.to_owned(),
+        mode.into(),
+        format,
+    );
     let error_handler = (&error_handler.0, &error_handler.1);
-    crate::generate_ir(error_handler, &ast)
+    crate::generate_ir::<M>(error_handler, &ast, parsed, Default::default(), format)
 }
 
 fn compile_tester(src: &str, tester: impl FnOnce(&ExecutionEngine)) {
-    let ir = test_generate_ir(src);
+    compile_tester_with::<DefaultMangler>(LexMode::Ascii, src, tester)
+}
+
+fn compile_tester_with<M: Mangle>(mode: LexMode, src: &str, tester: impl FnOnce(&ExecutionEngine)) {
+    let ir = test_generate_ir::<M>(src, mode);
     let backend = LLVMBackend::init(());
     let module = backend.module(src, &ir).unwrap();
     let ee = module
@@ -61,7 +73,7 @@ fn jia_jian_around_114514() {
 
 #[test]
 fn serde_test() {
-    let mir = test_generate_ir(MORE_OPERATOES);
+    let mir = test_generate_ir::<DefaultMangler>(MORE_OPERATOES, LexMode::Ascii);
 
     let str1 = serde_json::to_string(&mir).unwrap();
     let ast1: Vec<ir::Item> = serde_json::from_str(&str1).unwrap();
@@ -175,3 +187,565 @@ fn control_flow_test() {
         }
     })
 }
+
+const WHILE_LOOP: &str = "
+zheng3 sum can1 zheng3 n jie2
+han2
+    zheng3 i wei2 0 fen1
+    zheng3 s wei2 0 fen1
+    chong2 can1 i xiao3 n jie2
+    han2
+        i wei2 i jia1 1 fen1
+        s wei2 s jia1 i fen1
+    jie2
+    fan3 s fen1
+jie2
+";
+
+#[test]
+fn while_loop() {
+    // the condition block of the loop must be branched into from the block before the loop
+    compile_tester(WHILE_LOOP, |ee| unsafe {
+        type Sum = unsafe extern "C" fn(i64) -> i64;
+
+        let sum: JitFunction<Sum> = ee.get_function("sum 参 i64 结").unwrap();
+
+        for n in 0..20 {
+            assert_eq!(sum.call(n), n * (n + 1) / 2);
+        }
+    })
+}
+
+const BOOL_AND_NULL: &str = "
+bu4 zheng4 can1 zheng3 x jie2
+han2
+    bu4 ret wei2 jia3 fen1
+    ruo4 can1 x da4 0 jie2
+    han2
+        ret wei2 zhen1 fen1
+    jie2
+    fan3 ret fen1
+jie2
+
+zhi3 zheng3 kong can1 jie2
+han2
+    fan3 kong1zhi3 fen1
+jie2
+";
+
+#[test]
+fn bool_and_null_literals() {
+    compile_tester(BOOL_AND_NULL, |ee| unsafe {
+        type Zheng = unsafe extern "C" fn(i64) -> bool;
+        type Kong = unsafe extern "C" fn() -> *const i64;
+
+        let zheng: JitFunction<Zheng> = ee.get_function("zheng4 参 i64 结").unwrap();
+        let kong: JitFunction<Kong> = ee.get_function("kong 参 结").unwrap();
+
+        for n in -114..514 {
+            assert_eq!(zheng.call(n), n > 0);
+        }
+        assert!(kong.call().is_null());
+    })
+}
+
+const NUMBER_LITERALS: &str = "
+kuan1 8 zheng3 hex can1 jie2
+han2
+    fan3 0x7f fen1
+jie2
+
+zheng3 neg can1 zheng3 x jie2
+han2
+    fan3 fu4 x jia1 0b101 jia1 1_000 fen1
+jie2
+
+kuan1 64 fu2 exp can1 jie2
+han2
+    kuan1 64 fu2 x wei2 1f05e2 fen1
+    fan3 x jia1 25en2 fen1
+jie2
+
+wu2fu2 kuan1 128 zheng3 wide can1 jie2
+han2
+    fan3 0xffff_ffff_ffff_ffff_ffffu128 fen1
+jie2
+
+kuan1 8 zheng3 min can1 jie2
+han2
+    fan3 fu4 128 fen1
+jie2
+";
+
+#[test]
+fn number_literals() {
+    compile_tester(NUMBER_LITERALS, |ee| unsafe {
+        type Hex = unsafe extern "C" fn() -> i8;
+        type Neg = unsafe extern "C" fn(i64) -> i64;
+        type Exp = unsafe extern "C" fn() -> f64;
+        type Wide = unsafe extern "C" fn() -> u128;
+        type Min = unsafe extern "C" fn() -> i8;
+
+        let hex: JitFunction<Hex> = ee.get_function("hex 参 结").unwrap();
+        let neg: JitFunction<Neg> = ee.get_function("neg 参 i64 结").unwrap();
+        let exp: JitFunction<Exp> = ee.get_function("exp 参 结").unwrap();
+        let wide: JitFunction<Wide> = ee.get_function("wide 参 结").unwrap();
+        let min: JitFunction<Min> = ee.get_function("min 参 结").unwrap();
+
+        assert_eq!(hex.call(), 0x7f);
+        for n in -114..514 {
+            assert_eq!(neg.call(n), -n + 0b101 + 1_000);
+        }
+        assert_eq!(exp.call(), 1.05e2 + 25e-2);
+        assert_eq!(wide.call(), 0xffff_ffff_ffff_ffff_ffff);
+        assert_eq!(min.call(), i8::MIN);
+    })
+}
+
+const UNICODE_LITERALS: &str = "
+kuan1 32 wu2fu2 zheng3 zhong can1 jie2
+han2
+    fan3 wen2 中 fen1
+jie2
+
+zu3 kuan1 8 wu2fu2 zheng3 hello can1 jie2
+han2
+    fan3 chuan4 你好_u002c_s世界 fen1
+jie2
+";
+
+#[test]
+fn unicode_literals() {
+    compile_tester(UNICODE_LITERALS, |ee| unsafe {
+        type Zhong = unsafe extern "C" fn() -> u32;
+        type Hello = unsafe extern "C" fn() -> *const std::ffi::c_char;
+
+        let zhong: JitFunction<Zhong> = ee.get_function("zhong 参 结").unwrap();
+        let hello: JitFunction<Hello> = ee.get_function("hello 参 结").unwrap();
+
+        assert_eq!(zhong.call(), '中' as u32);
+        let hello = std::ffi::CStr::from_ptr(hello.call());
+        assert_eq!(hello.to_str(), Ok("你好, 世界"));
+    })
+}
+
+const HANZI: &str = "
+整 加一 参 整 甲 结
+函
+    整 结果 为 甲 加 1 分
+    若 参 结果 大 10 结
+    函
+        返 10 分
+    结
+    返 结果 分
+结
+";
+
+#[test]
+fn hanzi() {
+    compile_tester_with::<DefaultMangler>(LexMode::Unicode, HANZI, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let jia_yi: JitFunction<TestFn> = ee.get_function("加一 参 i64 结").unwrap();
+
+        for n in -114..514 {
+            assert_eq!(jia_yi.call(n), (n + 1).min(10));
+        }
+    })
+}
+
+#[test]
+fn ascii_mangler() {
+    compile_tester_with::<AsciiMangler>(LexMode::Unicode, HANZI, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let jia_yi: JitFunction<TestFn> = ee.get_function("_Zu12_52a0__4e00_x").unwrap();
+
+        for n in -114..514 {
+            assert_eq!(jia_yi.call(n), (n + 1).min(10));
+        }
+    })
+}
+
+const CONDITIONAL_EXPR: &str = "
+zheng3 sign can1 zheng3 x jie2
+han2
+    zheng3 one wei2 1 fen1
+    fan3 ruo4 can1 x xiao3 0 jie2 fu4 one ze2 ruo4 can1 x tong2 0 jie2 x ze2 one fen1
+jie2
+
+zheng3 distance can1 zheng3 n jie2
+han2
+    zheng3 sum wei2 0 fen1
+    zheng3 i wei2 0 fen1
+    chong2 can1 i xiao3 n jie2 han2
+        sum wei2 sum jia1 ruo4 can1 i cheng2 2 xiao3 n jie2 n jian3 i cheng2 2 ze2 i cheng2 2 jian3 n fen1
+        i wei2 i jia1 1 fen1
+    jie2
+    fan3 sum fen1
+jie2
+";
+
+#[test]
+fn conditional_expression() {
+    compile_tester(CONDITIONAL_EXPR, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let sign: JitFunction<TestFn> = ee.get_function("sign 参 i64 结").unwrap();
+        let distance: JitFunction<TestFn> = ee.get_function("distance 参 i64 结").unwrap();
+
+        for n in -114..514 {
+            assert_eq!(sign.call(n), n.signum());
+        }
+        for n in 0..100 {
+            let native = (0..n).map(|i: i64| (i * 2 - n).abs()).sum::<i64>();
+            assert_eq!(distance.call(n), native);
+        }
+    })
+}
+
+const FOR_LOOP: &str = "
+zheng3 squares can1 zheng3 n jie2
+han2
+    zheng3 sum wei2 0 fen1
+    xun2huan2 can1 zheng3 i wei2 0 fen1 i xiao3 n fen1 i wei2 i jia1 1 jie2 han2
+        sum wei2 sum jia1 i cheng2 i fen1
+    jie2
+    zhu4 the loop variable is not visible here, so it can be defined again
+    xun2huan2 can1 zheng3 i wei2 n fen1 i da4 0 fen1 i wei2 i jian3 1 jie2 han2
+        sum wei2 sum jia1 i fen1
+    jie2
+    fan3 sum fen1
+jie2
+";
+
+#[test]
+fn for_loop() {
+    compile_tester(FOR_LOOP, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let squares: JitFunction<TestFn> = ee.get_function("squares 参 i64 结").unwrap();
+
+        for n in 0..100 {
+            let native = (0..n).map(|i| i * i).sum::<i64>() + (1..=n).sum::<i64>();
+            assert_eq!(squares.call(n), native);
+        }
+    })
+}
+
+const INFINITE_LOOP: &str = "
+zheng3 root can1 zheng3 n jie2
+han2
+    zheng3 i wei2 0 fen1
+    chong2 can1 jie2 han2
+        ruo4 can1 i cheng2 i da4 n jie2 han2
+            fan3 i jian3 1 fen1
+        jie2
+        i wei2 i jia1 1 fen1
+    jie2
+jie2
+";
+
+#[test]
+fn infinite_loop() {
+    compile_tester(INFINITE_LOOP, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let root: JitFunction<TestFn> = ee.get_function("root 参 i64 结").unwrap();
+
+        for n in 0..1000 {
+            assert_eq!(root.call(n), (n as f64).sqrt() as i64);
+        }
+    })
+}
+
+const COMPOUND_ASSIGN: &str = "
+zheng3 mix can1 zheng3 n jie2
+han2
+    zheng3 acc wei2 1 fen1
+    xun2huan2 can1 zheng3 i wei2 0 fen1 i xiao3 n fen1 i jia1 wei2 1 jie2 han2
+        acc cheng2 wei2 3 fen1
+        acc jian3 wei2 i fen1
+        acc mo2 wei2 1000003 fen1
+        acc wei4yi4huo4 wei2 i zuo3yi2 2 fen1
+    jie2
+    fan3 acc fen1
+jie2
+";
+
+#[test]
+fn compound_assign() {
+    compile_tester(COMPOUND_ASSIGN, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let mix: JitFunction<TestFn> = ee.get_function("mix 参 i64 结").unwrap();
+
+        fn native_mix(n: i64) -> i64 {
+            let mut acc = 1;
+            for i in 0..n {
+                acc *= 3;
+                acc -= i;
+                acc %= 1000003;
+                acc ^= i << 2;
+            }
+            acc
+        }
+
+        for n in 0..100 {
+            assert_eq!(mix.call(n), native_mix(n));
+        }
+    })
+}
+
+const TYPE_INFERENCE: &str = "
+zheng3 collatz can1 zheng3 n jie2
+han2
+    ling4 steps wei2 n jian3 n fen1
+    ling4 x wei2 n fen1
+    chong2 can1 x fei1tong2 1 jie2 han2
+        ling4 odd wei2 x mo2 2 tong2 1 fen1
+        x wei2 ruo4 can1 odd jie2 x cheng2 3 jia1 1 ze2 x chu2 2 fen1
+        steps jia1 wei2 1 fen1
+    jie2
+    ling4 end wei2 steps jia1 3 fen1
+    xun2huan2 can1 ling4 i wei2 steps fen1 i xiao3 end fen1 i jia1 wei2 1 jie2 han2
+        steps jia1 wei2 i fen1
+    jie2
+    fan3 steps fen1
+jie2
+";
+
+#[test]
+fn type_inference() {
+    compile_tester(TYPE_INFERENCE, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let collatz: JitFunction<TestFn> = ee.get_function("collatz 参 i64 结").unwrap();
+
+        fn native_collatz(n: i64) -> i64 {
+            let (mut steps, mut x) = (0, n);
+            while x != 1 {
+                x = if x % 2 == 1 { x * 3 + 1 } else { x / 2 };
+                steps += 1;
+            }
+            let (start, end) = (steps, steps + 3);
+            for i in start..end {
+                steps += i;
+            }
+            steps
+        }
+
+        for n in 1..200 {
+            assert_eq!(collatz.call(n), native_collatz(n));
+        }
+    })
+}
+
+const RETURN_TYPE_INFERENCE: &str = "
+ling4 sq can1 zheng3 x jie2
+han2
+    fan3 x cheng2 x fen1
+jie2
+
+ling4 odd can1 zheng3 x jie2
+han2
+    ruo4 can1 x mo2 2 tong2 1 jie2 han2
+        fan3 zhen1 fen1
+    jie2
+    fan3 x mo2 2 fei1tong2 x mo2 2 fen1
+jie2
+
+ling4 odd_squares can1 zheng3 n jie2
+han2
+    ling4 acc wei2 n jian3 n fen1
+    xun2huan2 can1 ling4 i wei2 n jian3 n fen1 i xiao3 n fen1 i jia1 wei2 1 jie2 han2
+        ruo4 can1 ya1 i ru4 odd jie2 han2
+            acc jia1 wei2 ya1 i ru4 sq fen1
+        jie2
+    jie2
+    fan3 acc fen1
+jie2
+";
+
+#[test]
+fn return_type_inference() {
+    compile_tester(RETURN_TYPE_INFERENCE, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let odd_squares: JitFunction<TestFn> = ee.get_function("odd_squares 参 i64 结").unwrap();
+
+        fn native_odd_squares(n: i64) -> i64 {
+            (0..n).filter(|i| i % 2 == 1).map(|i| i * i).sum()
+        }
+
+        for n in 0..100 {
+            assert_eq!(odd_squares.call(n), native_odd_squares(n));
+        }
+    })
+}
+
+const IMPLICIT_CONVERSION: &str = "
+zheng3 twice can1 zheng3 x jie2
+han2
+    fan3 x cheng2 2 fen1
+jie2
+
+zheng3 pick can1 kuan1 32 zheng3 x jie2
+han2
+    fan3 1 fen1
+jie2
+
+zheng3 pick can1 zheng3 x jie2
+han2
+    fan3 2 fen1
+jie2
+
+kuan1 64 fu2 square can1 kuan1 64 fu2 x jie2
+han2
+    fan3 x cheng2 x fen1
+jie2
+
+zheng3 widen can1 kuan1 32 zheng3 n jie2
+han2
+    zheng3 big wei2 7 fen1
+    fan3 ya1 n ru4 twice jia1 ya1 n ru4 pick cheng2 10 jia1 ya1 big ru4 pick cheng2 100 fen1
+jie2
+
+kuan1 64 fu2 squaref can1 kuan1 32 zheng3 n jie2
+han2
+    fan3 ya1 n ru4 square fen1
+jie2
+";
+
+#[test]
+fn implicit_conversion() {
+    compile_tester(IMPLICIT_CONVERSION, |ee| unsafe {
+        type WidenFn = unsafe extern "C" fn(i32) -> i64;
+        type SquareFn = unsafe extern "C" fn(i32) -> f64;
+
+        let widen: JitFunction<WidenFn> = ee.get_function("widen 参 i32 结").unwrap();
+        let squaref: JitFunction<SquareFn> = ee.get_function("squaref 参 i32 结").unwrap();
+
+        for n in -100..100 {
+            assert_eq!(widen.call(n), n as i64 * 2 + 10 + 200);
+            assert_eq!(squaref.call(n), n as f64 * n as f64);
+        }
+    })
+}
+
+const LITERAL_DEFAULT: &str = "
+zheng3 f can1 kuan1 32 zheng3 x jie2
+han2
+    fan3 1 fen1
+jie2
+
+zheng3 f can1 zheng3 x jie2
+han2
+    fan3 2 fen1
+jie2
+
+zheng3 defaults can1 zheng3 n jie2
+han2
+    zheng3 a wei2 ya1 1 ru4 f fen1
+    ling4 b wei2 3 jia1 4 fen1
+    fan3 a cheng2 100 jia1 b jia1 n fen1
+jie2
+";
+
+#[test]
+fn literal_default() {
+    compile_tester(LITERAL_DEFAULT, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let defaults: JitFunction<TestFn> = ee.get_function("defaults 参 i64 结").unwrap();
+
+        for n in -100..100 {
+            assert_eq!(defaults.call(n), 2 * 100 + 7 + n);
+        }
+    })
+}
+
+const DEFINITE_ASSIGNMENT: &str = "
+zheng3 both can1 zheng3 n jie2
+han2
+    zheng3 x fen1
+    ruo4 can1 n da4 0 jie2
+    han2
+        x wei2 1 fen1
+    jie2 ze2 han2
+        x wei2 2 fen1
+    jie2
+    zheng3 y fen1
+    ruo4 can1 n xiao3 0 jie2
+    han2
+        fan3 x fen1
+    jie2
+    y wei2 x cheng2 10 fen1
+    y jia1 wei2 n fen1
+    fan3 y fen1
+jie2
+";
+
+#[test]
+fn definite_assignment() {
+    compile_tester(DEFINITE_ASSIGNMENT, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let both: JitFunction<TestFn> = ee.get_function("both 参 i64 结").unwrap();
+
+        for n in -100..100 {
+            let x = if n > 0 { 1 } else { 2 };
+            let expect = if n < 0 { x } else { x * 10 + n };
+            assert_eq!(both.call(n), expect);
+        }
+    })
+}
+
+const UNREACHABLE: &str = "
+zheng3 countdown can1 zheng3 n jie2
+han2
+    zheng3 x wei2 n fen1
+    ruo4 can1 x da4 100 jie2
+    han2
+        fan3 100 fen1
+        x wei2 0 fen1
+    jie2
+    chong2 can1 jia3 jie2
+    han2
+        fan3 0 fen1
+    jie2
+    chong2 can1 jie2
+    han2
+        ruo4 can1 x xiao3 0 jie2
+        han2
+            fan3 x fen1
+        jie2
+        x wei2 x jian3 1 fen1
+    jie2
+    fan3 1 fen1
+jie2
+
+zheng3 always can1 zheng3 n jie2
+han2
+    ruo4 can1 zhen1 jie2
+    han2
+        fan3 n fen1
+    jie2
+jie2
+";
+
+#[test]
+fn unreachable() {
+    compile_tester(UNREACHABLE, |ee| unsafe {
+        type TestFn = unsafe extern "C" fn(i64) -> i64;
+
+        let countdown: JitFunction<TestFn> = ee.get_function("countdown 参 i64 结").unwrap();
+        let always: JitFunction<TestFn> = ee.get_function("always 参 i64 结").unwrap();
+
+        for n in -100..200 {
+            let expect = if n > 100 { 100 } else { n.min(-1) };
+            assert_eq!(countdown.call(n), expect);
+            assert_eq!(always.call(n), n);
+        }
+    })
+}
@@ -86,10 +86,21 @@ impl<S: ToString> From<S> for Message {
     }
 }
 
+/// how serious an [`Error`] is
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// the compilation goes on, like an unused variable
+    Warning,
+    /// the compilation fails
+    #[default]
+    Error,
+}
+
 /// an error, with many messages in
 #[derive(Debug, Clone)]
 pub struct Error {
     pub(crate) messages: Vec<Message>,
+    pub(crate) severity: Severity,
 }
 
 impl Error {
@@ -97,14 +108,39 @@ impl Error {
     pub fn new(main_span: Span, reason: impl ToString) -> Self {
         Self {
             messages: vec![main_span.make_message(reason)],
+            severity: Severity::Error,
         }
     }
 
+    /// messages in the error, the first one is the reason of the error
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// how serious the error is, [`Severity::Error`] by default
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// set the [`Severity`], and return [`Error`] for chain-calling
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
     /// append an error [`Message`], and return [`Error`] for chain-calling
     pub fn append(mut self, message: impl Into<Message>) -> Self {
         self.messages.push(message.into());
         self
     }
+
+    /// the location of the first message, which is where the error happened
+    pub(crate) fn main_span(&self) -> Option<Span> {
+        match self.messages.first()? {
+            Message::Location(span) | Message::Rich(_, span) => Some(*span),
+            Message::Text(_) => None,
+        }
+    }
 }
 
 impl Extend<Message> for Error {
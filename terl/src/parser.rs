@@ -164,6 +164,8 @@ pub struct Parser<S: Source = char> {
     /// source codes
     src: Buffer<S>,
     state: ParserState,
+    /// errors which the parser has recovered from
+    errors: Vec<ParseError>,
     #[cfg(feature = "parser_calling_tree")]
     calling_tree: calling_tree::CallingTree,
 }
@@ -189,6 +191,7 @@ impl<S: Source> Parser<S> {
                 start: StartIdx::Init(0),
                 idx: 0,
             },
+            errors: vec![],
             #[cfg(feature = "parser_calling_tree")]
             calling_tree: Default::default(),
         }
@@ -275,6 +278,23 @@ impl<S: Source> Parser<S> {
         &self.calling_tree
     }
 
+    /// report an error which the parser has recovered from, so the parsing could go on
+    ///
+    /// errors reported in a failed [`Parser::once`] are dropped with the parsing result
+    pub fn report(&mut self, error: ParseError) {
+        self.errors.push(error);
+    }
+
+    /// errors which have been reported
+    pub fn reported(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    /// take all reported errors out
+    pub fn take_reported(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
     /// get a reference to the [`Buffer`] in [`Parser`]
     pub fn buffer(&self) -> &Buffer<S> {
         &self.src
@@ -317,10 +337,14 @@ impl<S: Source> Parser<S> {
         // create a temp parser and reset its state
 
         let state = self.state;
+        let reported = self.errors.len();
         self.state = self.state.fork();
 
         let result = self.once_no_try::<P, _>(parser);
         self.state = state.sync_with(&self.state, &result);
+        if result.is_err() {
+            self.errors.truncate(reported);
+        }
 
         result
     }
@@ -375,7 +399,17 @@ impl<'p, S: Source, P: ParseUnit<S>> Try<'p, P, S> {
 
         if self.state.is_none() || is_unmatch {
             let state = self.parser.once(parser);
-            self.state = Some(state);
+            self.state = match (self.state.take(), state) {
+                // the unmatched parser which went further explains the error better
+                (Some(Err(previous)), Err(state))
+                    if state.kind() == ParseErrorKind::Unmatch
+                        && previous.main_span().map(|span| span.end)
+                            > state.main_span().map(|span| span.end) =>
+                {
+                    Some(Err(previous))
+                }
+                (_, state) => Some(state),
+            };
         }
         self
     }
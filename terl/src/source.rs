@@ -12,7 +12,7 @@ pub trait Source: Sized {
     ///
     /// This function formats an error into a string. It iterates through the error's messages,
     /// formatting each one and appending it to the `buffer`. If any formatting fails,
-    /// it returns an error.
+    /// it returns an error. Warnings are started with `warning: `.
     ///
     /// # Parameters
     ///
@@ -25,6 +25,9 @@ pub trait Source: Sized {
     fn handle_error(with: &Self::HandleErrorWith<'_>, error: Error) -> String {
         (|| -> Result<_, std::fmt::Error> {
             let mut buffer = String::new();
+            if error.severity == Severity::Warning {
+                buffer.write_str("warning: ")?;
+            }
             for msg in error.messages {
                 Self::handle_message(with, &mut buffer, msg)?;
             }
@@ -89,7 +92,7 @@ pub trait Source: Sized {
 impl Source for char {
     type HandleErrorWith<'b> = Buffer<char>;
 
-    #[cfg(not(feature = "color"))]
+    /// the location is underlined if colors are enabled, or marked with `^` under it
     fn handle_location<S>(
         with: &Buffer<char>,
         buffer: &mut S,
@@ -99,104 +102,120 @@ impl Source for char {
     where
         S: Write,
     {
-        let src = with;
-        let start_line_start = (0..span.start)
-            .rev()
-            .find(|idx| src[*idx] == '\n')
-            .map(|idx| idx + 1)
-            .unwrap_or(0);
-        let mut line_num = (0..span.start).filter(|idx| src[*idx] == '\n').count() + 1;
-        let mut idx = start_line_start;
+        #[cfg(feature = "color")]
+        if colored::control::SHOULD_COLORIZE.should_colorize() {
+            return colored_location(with, buffer, span, msg);
+        }
+        plain_location(with, buffer, span, msg)
+    }
+}
 
-        let row_num = span.start - start_line_start + 1;
-        let location = format!("[{}:{}:{}]", src.name(), line_num, row_num,);
+/// enable or disable colors in formatted errors, they are enabled by default if the terminal
+/// supports them
+///
+/// colors are never used without the `color` feature
+pub fn set_colored(colored: bool) {
+    #[cfg(feature = "color")]
+    colored::control::set_override(colored);
+    #[cfg(not(feature = "color"))]
+    let _ = colored;
+}
 
-        writeln!(buffer, "{location}: {}", msg)?;
+fn plain_location<S>(src: &Buffer<char>, buffer: &mut S, span: Span, msg: &str) -> std::fmt::Result
+where
+    S: Write,
+{
+    let start_line_start = (0..span.start)
+        .rev()
+        .find(|idx| src[*idx] == '\n')
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let mut line_num = (0..span.start).filter(|idx| src[*idx] == '\n').count() + 1;
+    let mut idx = start_line_start;
 
-        while idx < span.end && idx < src.len() {
-            let line_start = idx;
+    let row_num = span.start - start_line_start + 1;
+    let location = format!("[{}:{}:{}]", src.name(), line_num, row_num,);
 
-            let head = format!("at line {line_num} | ");
+    writeln!(buffer, "{location}: {}", msg)?;
 
-            let mut space_len = head.len();
-            while idx < span.start {
-                space_len += 1;
-                idx += 1;
-            }
-            let mut hats = (0..space_len).map(|_| ' ').collect::<String>();
-            while idx < src.len() && src[idx] != '\n' {
-                if idx < span.end {
-                    hats.push('^');
-                }
-                idx += 1;
-            }
+    while idx < span.end && idx < src.len() {
+        let line_start = idx;
+
+        let head = format!("at line {line_num} | ");
 
-            let line = src[line_start..idx].iter().collect::<String>();
-            if !line.is_empty() {
-                writeln!(buffer, "{head}{line}")?;
-                writeln!(buffer, "{hats}")?;
+        let mut space_len = head.len();
+        while idx < span.start {
+            space_len += 1;
+            idx += 1;
+        }
+        let mut hats = (0..space_len).map(|_| ' ').collect::<String>();
+        while idx < src.len() && src[idx] != '\n' {
+            if idx < span.end {
+                hats.push('^');
             }
+            idx += 1;
+        }
 
-            idx = (idx + 1).min(src.len());
-            line_num += 1;
+        let line = src[line_start..idx].iter().collect::<String>();
+        if !line.is_empty() {
+            writeln!(buffer, "{head}{line}")?;
+            writeln!(buffer, "{hats}")?;
         }
-        Ok(())
+
+        idx = (idx + 1).min(src.len());
+        line_num += 1;
     }
+    Ok(())
+}
 
-    #[cfg(feature = "color")]
-    fn handle_location<S>(
-        src: &Self::HandleErrorWith<'_>,
-        buffer: &mut S,
-        loc: Span,
-        msg: &str,
-    ) -> std::fmt::Result
-    where
-        S: Write,
-    {
-        let start_line_start = (0..loc.start)
-            .rev()
-            .find(|idx| src[*idx] == '\n')
-            .map(|idx| idx + 1)
-            .unwrap_or(0);
-        let mut line_num = (0..loc.start).filter(|idx| src[*idx] == '\n').count() + 1;
-        let mut idx = start_line_start;
-
-        let row_num = loc.start - start_line_start + 1;
-        let location = format!("[{}:{}:{}]", src.name(), line_num, row_num,);
-
-        writeln!(buffer, "{location}: {}", msg)?;
-        while idx < loc.end && idx < src.len() {
-            buffer.write_fmt(format_args!("at line {line_num} | "))?;
-            let end = (idx..src.len())
-                .find(|pos| src[*pos] == '\n')
-                .unwrap_or(src.len());
-            use colored::Colorize;
-
-            if loc.start > idx {
-                for idx in idx..loc.start {
-                    buffer.write_char(src[idx])?;
-                }
+#[cfg(feature = "color")]
+fn colored_location<S>(src: &Buffer<char>, buffer: &mut S, loc: Span, msg: &str) -> std::fmt::Result
+where
+    S: Write,
+{
+    let start_line_start = (0..loc.start)
+        .rev()
+        .find(|idx| src[*idx] == '\n')
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let mut line_num = (0..loc.start).filter(|idx| src[*idx] == '\n').count() + 1;
+    let mut idx = start_line_start;
+
+    let row_num = loc.start - start_line_start + 1;
+    let location = format!("[{}:{}:{}]", src.name(), line_num, row_num,);
+
+    writeln!(buffer, "{location}: {}", msg)?;
+    while idx < loc.end && idx < src.len() {
+        buffer.write_fmt(format_args!("at line {line_num} | "))?;
+        let end = (idx..src.len())
+            .find(|pos| src[*pos] == '\n')
+            .unwrap_or(src.len());
+        use colored::Colorize;
+
+        if loc.start > idx {
+            for idx in idx..loc.start {
+                buffer.write_char(src[idx])?;
             }
+        }
+
+        let error = (loc.start.max(idx)..end.min(loc.end))
+            .map(|pos| src[pos])
+            .collect::<String>()
+            .red()
+            .underline();
 
-            let error = (loc.start.max(idx)..end.min(loc.end))
-                .map(|pos| src[pos])
-                .collect::<String>()
-                .red()
-                .underline();
-
-            // use foramt, or the output willnot be colored
-            buffer.write_fmt(format_args!("{error}"))?;
-            if loc.end < end - 1 {
-                for idx in loc.end..end {
-                    buffer.write_char(src[idx])?;
-                }
+        // use foramt, or the output willnot be colored
+        buffer.write_fmt(format_args!("{error}"))?;
+        if loc.end < end - 1 {
+            for idx in loc.end..end {
+                buffer.write_char(src[idx])?;
             }
-            idx = end;
-            line_num -= 1;
-            writeln!(buffer)?;
         }
-        Ok(())
+        idx = (end + 1).min(src.len());
+        line_num += 1;
+        writeln!(buffer)?;
     }
+    Ok(())
 }
 
 /// a buffer,store source whihch is needed by [`Parser`] in
@@ -248,3 +267,27 @@ where
         &self.src[index]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_line_location() {
+        // the colored renderer is the one which once failed on spans across lines
+        #[cfg(feature = "color")]
+        colored::control::set_override(true);
+
+        let src = Buffer::new("test.py1".to_owned(), "ab\ncd\nef".chars().collect());
+        let error = Span::new(1, 7).make_error("across lines");
+        let rendered = char::handle_error(&src, error);
+        assert!(rendered.starts_with("[test.py1:1:2]: across lines"));
+        for line in 1..=3 {
+            assert!(
+                rendered.contains(&format!("at line {line} | ")),
+                "{rendered}"
+            );
+        }
+        assert!(!rendered.contains("at line 4"));
+    }
+}